@@ -0,0 +1,159 @@
+// times `CPU::run` for every scheduler over generated workloads, from a handful of processes to a hundred thousand
+// cargo bench, or cargo bench -- sjf to only run the cases whose label contains `sjf`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashSet;
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::sink::DiscardSink;
+use scheduler_gpt::schedulings::workload::{self, build, horizon, model, WorkloadSpec};
+
+/// Passes every allocation on to the system allocator, keeping track of the most memory in use at once
+struct Counting;
+
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            let in_use = IN_USE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(in_use, Ordering::Relaxed);
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Small cases are run again until this much time has passed, for a steadier average
+const MIN_TIME: Duration = Duration::from_millis(300);
+
+/// A workload size and how long it runs for
+struct Size {
+    processes: usize,
+    /// The most ticks between arrivals, 0 has everything waiting from the start
+    max_gap: i32,
+    /// None runs until every process could have finished
+    runfor: Option<i32>,
+}
+
+const SIZES: [Size; 6] = [
+    Size { processes: 10, max_gap: 3, runfor: None },
+    // almost all of it idle, which still writes a line every tick
+    Size { processes: 10, max_gap: 3, runfor: Some(1_000_000) },
+    Size { processes: 1_000, max_gap: 3, runfor: None },
+    Size { processes: 1_000, max_gap: 0, runfor: None },
+    // arrivals a thousand ticks apart on average, so the cpu is busy on and off across the whole run
+    Size { processes: 1_000, max_gap: 2_000, runfor: Some(1_000_000) },
+    // a full run of this many is far too long, so it is cut short with every process already waiting
+    Size { processes: 100_000, max_gap: 0, runfor: Some(100) },
+];
+
+/// The first configuration of every scheduler the tests run
+fn schedulers() -> Vec<(&'static str, Value)> {
+    let mut seen = HashSet::new();
+    workload::schedulers().into_iter().filter(|(keyword, _)| seen.insert(*keyword)).collect()
+}
+
+/// Where the log of a run goes, kept like `CPU::default` does or thrown away to measure the engine on its own
+#[derive(Clone, Copy)]
+enum Log {
+    Memory,
+    Discard,
+}
+
+impl Log {
+    const ALL: [Log; 2] = [Log::Memory, Log::Discard];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Log::Memory => "memory",
+            Log::Discard => "discard",
+        }
+    }
+
+    fn cpu(&self) -> CPU {
+        match self {
+            Log::Memory => CPU::default(),
+            Log::Discard => CPU::with_sink(Box::new(DiscardSink::default())),
+        }
+    }
+}
+
+/// What one case measured
+struct Measured {
+    runs: u32,
+    ticks: i64,
+    elapsed: Duration,
+    /// The most memory in use during a run, over what was in use before it started
+    peak_bytes: usize,
+    lines: usize,
+}
+
+fn measure(keyword: &str, params: &Value, size: &Size, log: Log) -> Measured {
+    let spec = WorkloadSpec { processes: size.processes, max_gap: size.max_gap, ..WorkloadSpec::default() };
+    let processes = spec.for_scheduler(keyword).generate(size.processes as u64);
+    let runfor = size.runfor.unwrap_or_else(|| horizon(&processes));
+
+    let mut measured = Measured { runs: 0, ticks: 0, elapsed: Duration::ZERO, peak_bytes: 0, lines: 0 };
+
+    while measured.runs == 0 || measured.elapsed < MIN_TIME {
+        let mut model = model(processes.clone(), build(keyword, params).unwrap(), runfor).unwrap();
+        let mut cpu = log.cpu();
+
+        let before = IN_USE.load(Ordering::Relaxed);
+        PEAK.store(before, Ordering::Relaxed);
+        let start = Instant::now();
+
+        cpu.run(&mut model);
+
+        measured.elapsed += start.elapsed();
+        measured.peak_bytes = measured.peak_bytes.max(PEAK.load(Ordering::Relaxed) - before);
+        measured.lines = cpu.line_count();
+        measured.ticks += runfor as i64;
+        measured.runs += 1;
+    }
+
+    measured
+}
+
+fn main() {
+    // cargo passes --bench, anything else narrows down the cases
+    let filters: Vec<String> = env::args().skip(1).filter(|arg| !arg.starts_with("--")).collect();
+
+    println!("{:<13} {:>9} {:>9} {:<7} {:>5} {:>12} {:>14} {:>11} {:>10}", "scheduler", "processes", "runfor", "log", "runs", "ms per run", "ticks per sec", "peak MiB", "lines");
+
+    for (keyword, params) in schedulers() {
+        for size in &SIZES {
+            for log in Log::ALL {
+                let label = format!("{} {} {}", keyword, size.processes, log.name());
+                if !filters.is_empty() && !filters.iter().any(|filter| label.contains(filter.as_str())) {
+                    continue;
+                }
+
+                let measured = measure(keyword, &params, size, log);
+                let seconds = measured.elapsed.as_secs_f64();
+
+                println!(
+                    "{:<13} {:>9} {:>9} {:<7} {:>5} {:>12.2} {:>14.0} {:>11.2} {:>10}",
+                    keyword, size.processes, measured.ticks / measured.runs as i64, log.name(), measured.runs,
+                    seconds * 1000.0 / measured.runs as f64, measured.ticks as f64 / seconds,
+                    measured.peak_bytes as f64 / (1024.0 * 1024.0), measured.lines
+                );
+            }
+        }
+    }
+}
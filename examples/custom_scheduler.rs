@@ -0,0 +1,93 @@
+// adds a scheduler from outside the crate and runs a scenario with it
+// cargo run --example custom_scheduler
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::process::{Process, ProcessState};
+use scheduler_gpt::schedulings::registry::{register, restore_as, Registration};
+use scheduler_gpt::schedulings::schedule::read_contents;
+use scheduler_gpt::schedulings::scheduler::Scheduler;
+
+/// Last-Come First-Served, the newest waiting process runs next and keeps the cpu until it finishes
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct Lcfs {
+    stack: VecDeque<String>,
+    selected: Option<String>,
+}
+
+impl Scheduler for Lcfs {
+    fn on_arrive(&mut self, process: &mut Process, _: i32) {
+        self.stack.push_front(process.name.to_owned());
+    }
+
+    fn pre_tick(&mut self, process: &mut Process, time: i32) {
+        if self.selected.is_none() {
+            self.selected = self.stack.pop_front();
+        }
+
+        if self.selected.as_ref() == Some(&process.name) && process.state != Some(ProcessState::Running) {
+            process.select(time);
+        }
+    }
+
+    fn on_tick(&mut self, _: &mut Process, _: i32) {}
+
+    fn on_finish(&mut self, _: &Process, _: i32) {
+        self.selected = None;
+    }
+
+    fn selected_process_name(&self) -> Option<String> {
+        self.selected.clone()
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        self.stack.iter().cloned().collect()
+    }
+
+    fn descriptive_name(&self) -> String {
+        "Last-Come First-Served".to_string()
+    }
+
+    fn keyword(&self) -> String {
+        "lcfs".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+}
+
+const SCENARIO: &str = "
+runfor 15
+use lcfs
+process name P1 arrival 0 burst 4
+process name P2 arrival 1 burst 3
+process name P3 arrival 2 burst 2
+end
+";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    register(Registration {
+        keyword: "lcfs",
+        description: "Last-Come First-Served, the newest waiting process runs next",
+        params: &[],
+        build: |_| Ok(Box::new(Lcfs::default())),
+        restore: restore_as::<Lcfs>,
+    })?;
+
+    let mut model = read_contents(SCENARIO.as_bytes())?;
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+    cpu.write_output(&mut io::stdout().lock())?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+// the fields of a `process` line, split the same way the file reader splits them
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use scheduler_gpt::schedulings::process::Process;
+
+fuzz_target!(|data: &[u8]| {
+    let line = String::from_utf8_lossy(data);
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let _ = Process::parse(&fields);
+});
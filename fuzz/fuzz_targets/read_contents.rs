@@ -0,0 +1,11 @@
+// a whole `.in` file of arbitrary bytes has to come back as a model or an error, never a panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use scheduler_gpt::schedulings::schedule::read_contents;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = read_contents(data);
+});
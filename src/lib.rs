@@ -0,0 +1,5 @@
+// the simulator as a library, other crates can run scenarios and add their own schedulers through `schedulings::registry`
+
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod schedulings;
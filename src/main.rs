@@ -1,44 +1,429 @@
-// to begin use cargo run [name of file]
+// to begin use cargo run [name of file], which can be an .in, .toml or .json scenario
+// run cargo run -- --help to see every option, or cargo run -- batch [directory or glob] to run many at once
+// compare and sweep run one scenario under every scheduler or a range of rr quantums, schedulers lists what `use` can pick
 
-mod schedulings;
-
-use std::env;
+use std::error::Error;
 use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use scheduler_gpt::schedulings::batch::{find_scenarios, run_file, run_model, write_summary};
+use scheduler_gpt::schedulings::bounds::{write_gaps, LowerBounds};
+use scheduler_gpt::schedulings::compare::{compare_models, sweep_models};
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::debugger::Debugger;
+use scheduler_gpt::schedulings::fairness::FairnessLimits;
+use scheduler_gpt::schedulings::optimal::{check, write_checks};
+use scheduler_gpt::schedulings::parallel::{default_jobs, map_parallel};
+use scheduler_gpt::schedulings::reference::{difftest, Reference};
+use scheduler_gpt::schedulings::registry::registrations;
+use scheduler_gpt::schedulings::report::{write_process_csv, write_report, write_timeline_csv, Format};
+use scheduler_gpt::schedulings::scenario::{read_file, OutputOptions};
+use scheduler_gpt::schedulings::schedule::ScheduleModel;
+use scheduler_gpt::schedulings::sink::{DiscardSink, Sink, WriteSink};
+use scheduler_gpt::schedulings::snapshot::{run_with_checkpoint, Checkpoint};
+use scheduler_gpt::schedulings::timeline::{TickRow, Timeline};
+use scheduler_gpt::schedulings::tui::Player;
+use scheduler_gpt::schedulings::workload::{horizon, WorkloadSpec};
+
+/// Simulates cpu scheduling algorithms over a scenario file
+#[derive(Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Scenario to run (.in, .toml or .json), or - to read an .in scenario from stdin
+    #[arg(required = true)]
+    input: Option<String>,
+
+    /// Where to write the results, - for stdout [default: the input with an .out extension, or stdout when reading stdin]
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// How to write the results
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Also log when a process is preempted or its quantum expires
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// End the output with every process's longest ready streak, flagging any that starved
+    #[arg(long)]
+    fairness: bool,
+
+    /// With --fairness, flag a process that waited more than this many times its burst
+    #[arg(long, value_name = "RATIO", default_value_t = 3.0, requires = "fairness")]
+    wait_ratio: f64,
+
+    /// With --fairness, flag a process that was ready for more than this many ticks in a row
+    #[arg(long, value_name = "TICKS", default_value_t = 10, requires = "fairness")]
+    max_streak: i32,
+
+    /// End the output with the lower bounds no scheduler could beat for the workload, and how far this run came from them
+    #[arg(long)]
+    bounds: bool,
+
+    /// With --bounds, how many cpus the bounds assume
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(i32).range(1..), requires = "bounds")]
+    cores: i32,
+
+    /// Pause after every tick to inspect the run
+    #[arg(long, conflicts_with_all = ["tui", "checkpoint_at"])]
+    step: bool,
 
-use schedulings::cpu::CPU;
+    /// Play the run back in a full screen terminal ui
+    #[arg(long, conflicts_with = "checkpoint_at")]
+    tui: bool,
 
-fn main() {
-    // parses command line
-    let args: Vec<String> = env::args().collect();
+    /// Save the run right after this tick, next to the input with a .ckpt extension
+    #[arg(long, value_name = "TIME")]
+    checkpoint_at: Option<i32>,
 
-    // checks if number of arguments is correct
-    if args.len() != 2 {
-        panic!("Use one input file");
+    /// Treat the input as a checkpoint and finish its run
+    #[arg(long, conflicts_with_all = ["step", "tui", "checkpoint_at"])]
+    resume: bool,
+
+    /// Also write a CSV row per process (name, arrival, burst, finish time, wait, turnaround, response, preemptions, selections, slices, finished), - for stdout
+    #[arg(long, value_name = "PATH")]
+    processes_csv: Option<String>,
+
+    /// Also write a CSV row per tick (time, running process, ready queue length), - for stdout
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["step", "tui", "checkpoint_at", "resume"])]
+    timeline_csv: Option<String>,
+}
+
+impl Cli {
+    fn fairness_limits(&self) -> Option<FairnessLimits> {
+        self.fairness.then_some(FairnessLimits {
+            wait_ratio: self.wait_ratio,
+            max_streak: self.max_streak,
+        })
     }
 
-    // takes input file name listed in command line
-    let input_file_name = &args[1];
-    let input_file = File::open(&args[1]);
+    fn bounds_cores(&self) -> Option<i32> {
+        self.bounds.then_some(self.cores)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run every scenario in a directory or matching a glob, writing each .out and printing a summary table
+    Batch {
+        /// A directory (searched recursively for .in, .toml and .json files) or a glob such as "tests/*.in"
+        pattern: String,
+
+        /// How to write each output file
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        #[command(flatten)]
+        jobs: Jobs,
+    },
+    /// Run one scenario under every built in scheduler and print their results side by side, and how far each is from the lower bounds
+    Compare {
+        /// Scenario to run (.in, .toml or .json), or - for stdin
+        input: String,
+
+        /// The quantum rr runs with
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(i32).range(1..))]
+        quantum: i32,
+
+        #[command(flatten)]
+        cores: Cores,
+
+        #[command(flatten)]
+        jobs: Jobs,
+    },
+    /// Run one scenario under rr with every quantum in a range
+    Sweep {
+        /// Scenario to run (.in, .toml or .json), or - for stdin
+        input: String,
+
+        /// The smallest quantum to try
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(i32).range(1..))]
+        min: i32,
 
-    // checks if valid file
-    let file: File = match input_file {
-        Ok(file) => file,
-        Err(error) => {
-            panic!("Error opening file: {}", error);
-        }    
+        /// The largest quantum to try
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(i32).range(1..))]
+        max: i32,
+
+        #[command(flatten)]
+        cores: Cores,
+
+        #[command(flatten)]
+        jobs: Jobs,
+    },
+    /// Find the lowest average wait any schedule could reach for a small scenario and compare realSJF and sjf against it
+    Optimal {
+        /// Scenario to run (.in, .toml or .json), or - for stdin
+        input: String,
+    },
+    /// Run generated workloads through the engine and a naive reference simulator of fcfs, sjf, realSJF and rr, printing where their logs first differ
+    Difftest {
+        /// How many workloads to generate for each scheduler
+        #[arg(long, default_value_t = 100)]
+        seeds: u64,
+
+        /// How many processes each workload has
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+        processes: u16,
+    },
+    /// List every scheduler a `use` line can pick, with the parameters it takes
+    Schedulers,
+}
+
+#[derive(clap::Args)]
+struct Jobs {
+    /// How many runs to execute at once [default: one per core]
+    #[arg(short, long, value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+}
+
+impl Jobs {
+    fn count(&self) -> usize {
+        self.jobs.map(usize::from).unwrap_or_else(default_jobs)
+    }
+}
+
+#[derive(clap::Args)]
+struct Cores {
+    /// How many cpus the lower bounds assume
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(i32).range(1..))]
+    cores: i32,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Some(Command::Batch { pattern, format, jobs }) => batch(pattern, *format, jobs.count()),
+        Some(Command::Compare { input, quantum, cores, jobs }) => read_file(input)
+            .map_err(|e| format!("could not read {}: {}", input, e).into())
+            .and_then(|(model, _)| Ok((LowerBounds::of(&model.process_list, cores.cores), compare_models(&model, *quantum)?)))
+            .and_then(|(bounds, models)| side_by_side(models, &bounds, jobs.count())),
+        Some(Command::Sweep { input, min, max, cores, jobs }) => read_file(input)
+            .map_err(|e| format!("could not read {}: {}", input, e).into())
+            .and_then(|(model, _)| Ok((LowerBounds::of(&model.process_list, cores.cores), sweep_models(&model, *min..=*max)?)))
+            .and_then(|(bounds, models)| side_by_side(models, &bounds, jobs.count())),
+        Some(Command::Optimal { input }) => read_file(input)
+            .map_err(|e| format!("could not read {}: {}", input, e).into())
+            .and_then(|(model, _)| check(&model))
+            .and_then(|checks| Ok(write_checks(&mut io::stdout().lock(), &checks)?))
+            .map(|()| ExitCode::SUCCESS),
+        Some(Command::Difftest { seeds, processes }) => difftest_seeds(*seeds, usize::from(*processes)),
+        Some(Command::Schedulers) => list_schedulers().map(|()| ExitCode::SUCCESS),
+        None => run(&cli).map(|()| ExitCode::SUCCESS),
     };
 
-    // reads file into a scheduling struct
-    match schedulings::schedule::read_contents(file){
-        Ok(schedule_model) => {
-            let mut cpu = CPU::default();
-            cpu.run(schedule_model);
-            let _ = cpu.write_output_file(&input_file_name.replace(".in", ".out"));
-        }
+    match result {
+        Ok(code) => code,
+        // a reader like `head` hanging up early is not a failure of ours
+        Err(e) if e.downcast_ref::<io::Error>().is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) => ExitCode::SUCCESS,
         Err(e) => {
-            panic!("Error reading file: {}", e);
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
         }
     }
 }
 
+fn run(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    let input = cli.input.as_deref().unwrap_or("-");
+    if cli.step && input == "-" {
+        return Err("--step reads its commands from stdin, so the scenario has to come from a file rather than -".into());
+    }
+
+    // the debugger, the player and checkpoints read the log back, any other text run goes straight to the output
+    let reads_log = cli.resume || cli.step || cli.tui || cli.checkpoint_at.is_some();
+    let streaming = cli.format == Format::Text && !reads_log;
+
+    let (mut cpu, schedule_model, output_options) = if cli.resume {
+        let checkpoint = Checkpoint::load(input)
+            .map_err(|e| format!("could not read checkpoint {}: {}", input, e))?;
+
+        let next_tick = checkpoint.time + 1;
+        let (mut cpu, mut schedule_model) = checkpoint.restore()?;
+        cpu.verbose = cli.verbose;
+        cpu.fairness = cli.fairness_limits();
+        cpu.bounds = cli.bounds_cores();
+        cpu.resume(&mut schedule_model, next_tick);
+
+        (cpu, schedule_model, OutputOptions::default())
+    }
+    else {
+        // reads file into a scheduling struct
+        let (mut schedule_model, output_options) = read_file(input)
+            .map_err(|e| format!("could not read {}: {}", input, e))?;
 
+        let mut cpu = if streaming {
+            CPU::with_sink(open_sink(&output_path(cli, input, &output_options))?)
+        }
+        else if cli.format == Format::Csv && !reads_log {
+            // one row per process is all csv writes, the log is never looked at
+            CPU::with_sink(Box::new(DiscardSink::default()))
+        }
+        else {
+            CPU::default()
+        };
+        cpu.verbose = cli.verbose;
+        cpu.fairness = cli.fairness_limits();
+        cpu.bounds = cli.bounds_cores();
+
+        if let Some(timeline_path) = &cli.timeline_csv {
+            let mut rows = vec![];
+            cpu.run_with(&mut schedule_model, |_, model, t| rows.push(TickRow::capture(model, t)));
+            // both may be going to stdout, so the log has to be out of the way first
+            cpu.flush()?;
+            write_to(timeline_path, |out| write_timeline_csv(out, &rows))?;
+        }
+        else if cli.step {
+            Debugger::stdio().run(&mut cpu, &mut schedule_model)?;
+        }
+        else if cli.tui {
+            let timeline = Timeline::record(&mut cpu, &mut schedule_model)?;
+            Player::new(&timeline).play()?;
+        }
+        else if let Some(time) = cli.checkpoint_at {
+            if input == "-" {
+                return Err("--checkpoint-at needs an input file to save next to".into());
+            }
+            run_with_checkpoint(&mut cpu, &mut schedule_model, time, &with_extension(input, "ckpt"))?;
+        }
+        else {
+            cpu.run(&mut schedule_model);
+        }
+
+        (cpu, schedule_model, output_options)
+    };
+
+    // a streamed log is already written, it only has to be flushed
+    if streaming {
+        cpu.flush()?;
+    }
+    else {
+        write_to(&output_path(cli, input, &output_options), |out| write_report(out, cli.format, &cpu, &schedule_model))?;
+    }
+
+    if let Some(processes_path) = &cli.processes_csv {
+        write_to(processes_path, |out| write_process_csv(out, &schedule_model.process_list))?;
+    }
+
+    Ok(())
+}
+
+/// Opens the path, or stdout for `-`, and hands it to `write`
+fn write_to(path: &str, write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> Result<(), Box<dyn Error>> {
+    if path == "-" {
+        write(&mut io::stdout().lock())?;
+        return Ok(());
+    }
+
+    let file = File::create(path).map_err(|e| format!("could not create {}: {}", path, e))?;
+    let mut out = BufWriter::new(file);
+    write(&mut out)?;
+    out.flush()?;
+
+    Ok(())
+}
+
+/// Where the results go, from the command line, then the scenario, then next to the input
+fn output_path(cli: &Cli, input: &str, output_options: &OutputOptions) -> String {
+    cli.output.clone()
+        .or(output_options.path.clone())
+        .unwrap_or_else(|| default_output_path(input))
+}
+
+/// A sink writing the log to the path as it runs, or to stdout for `-`
+fn open_sink(path: &str) -> Result<Box<dyn Sink>, Box<dyn Error>> {
+    if path == "-" {
+        return Ok(Box::new(WriteSink::new(io::stdout())));
+    }
+
+    let file = File::create(path).map_err(|e| format!("could not create {}: {}", path, e))?;
+    Ok(Box::new(WriteSink::new(file)))
+}
+
+/// The input with an .out extension, or stdout when the input is stdin
+fn default_output_path(input_file_name: &str) -> String {
+    if input_file_name == "-" {
+        return "-".to_string();
+    }
+
+    with_extension(input_file_name, "out")
+}
+
+fn with_extension(file_name: &str, extension: &str) -> String {
+    Path::new(file_name).with_extension(extension).to_string_lossy().into_owned()
+}
+
+/// Runs every scenario the pattern finds, failing only once all of them have run
+fn batch(pattern: &str, format: Format, jobs: usize) -> Result<ExitCode, Box<dyn Error>> {
+    let files = find_scenarios(pattern)?;
+    if files.is_empty() {
+        return Err(format!("no scenarios found for {}", pattern).into());
+    }
+
+    let results = map_parallel(jobs, files, |file| run_file(&file, format));
+    write_summary(&mut io::stdout().lock(), "file", &results)?;
+
+    if results.iter().any(|r| r.failed()) {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Runs every labelled model and prints one summary row for each, then how far each is from the bounds
+fn side_by_side(models: Vec<(String, ScheduleModel)>, bounds: &LowerBounds, jobs: usize) -> Result<ExitCode, Box<dyn Error>> {
+    if models.is_empty() {
+        return Err("nothing to run".into());
+    }
+
+    let results = map_parallel(jobs, models, |(label, model)| run_model(label, model));
+    let mut out = io::stdout().lock();
+    write_summary(&mut out, "run", &results)?;
+    write_gaps(&mut out, bounds, "run", &results)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Holds the engine against the reference for every seed, failing if any log differs
+fn difftest_seeds(seeds: u64, processes: usize) -> Result<ExitCode, Box<dyn Error>> {
+    let spec = WorkloadSpec { processes, ..WorkloadSpec::default() };
+    let mut out = io::stdout().lock();
+    let mut differed = 0;
+
+    for reference in Reference::ALL {
+        for seed in 0..seeds {
+            let workload = spec.generate(seed);
+            if let Some(divergence) = difftest(reference, &workload, horizon(&workload))? {
+                writeln!(out, "{} seed {}: {}", reference, seed, divergence)?;
+                differed += 1;
+            }
+        }
+    }
+
+    let runs = seeds * Reference::ALL.len() as u64;
+    writeln!(out, "{} runs, {} matched, {} differed", runs, runs - differed, differed)?;
+
+    match differed {
+        0 => Ok(ExitCode::SUCCESS),
+        _ => Ok(ExitCode::FAILURE),
+    }
+}
+
+fn list_schedulers() -> Result<(), Box<dyn Error>> {
+    let mut out = io::stdout().lock();
+    for registration in registrations() {
+        match registration.params {
+            [] => writeln!(out, "{}", registration.keyword)?,
+            params => writeln!(out, "{} (takes {})", registration.keyword, params.join(", "))?,
+        }
+        writeln!(out, "    {}", registration.description)?;
+    }
+
+    Ok(())
+}
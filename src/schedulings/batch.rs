@@ -0,0 +1,190 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufWriter, Result, Write};
+use std::path::{Path, PathBuf};
+
+use super::{
+    bounds::makespan,
+    cpu::CPU,
+    report::{write_report, Format},
+    scenario::read_file,
+    schedule::ScheduleModel,
+    sink::{DiscardSink, WriteSink},
+};
+
+/// The file extensions picked up when a directory is given
+const SCENARIO_EXTENSIONS: [&str; 3] = ["in", "toml", "json"];
+
+/// The headline numbers of a finished run
+pub struct RunSummary {
+    pub scheduler: String,
+    pub processes: usize,
+    pub finished: usize,
+    pub average_wait: f64,
+    pub average_turnaround: f64,
+    pub average_response: f64,
+    /// Totals over every process, finished or not
+    pub preemptions: i32,
+    pub slices: i32,
+    /// When the last process finished, if they all did
+    pub makespan: Option<i32>,
+}
+
+impl RunSummary {
+    /// Averages are taken over the processes that finished
+    pub fn from_model(model: &ScheduleModel) -> RunSummary {
+        let finished: Vec<_> = model.process_list.iter().filter(|p| p.finished()).collect();
+        let average = |total: i32| {
+            if finished.is_empty() {
+                0.0
+            }
+            else {
+                total as f64 / finished.len() as f64
+            }
+        };
+
+        RunSummary {
+            scheduler: model.scheduler.keyword(),
+            processes: model.process_list.len(),
+            finished: finished.len(),
+            average_wait: average(finished.iter().map(|p| p.wait_time).sum()),
+            average_turnaround: average(finished.iter().map(|p| p.turnaround_time).sum()),
+            average_response: average(finished.iter().map(|p| p.response_time).sum()),
+            preemptions: model.process_list.iter().map(|p| p.preemptions).sum(),
+            slices: model.process_list.iter().map(|p| p.slices).sum(),
+            makespan: makespan(&model.process_list),
+        }
+    }
+}
+
+/// How one run of a batch went, failures are kept as messages so the rest of the batch still runs
+pub struct BatchResult {
+    /// The file or configuration the run is listed under
+    pub label: String,
+    pub outcome: std::result::Result<RunSummary, String>,
+}
+
+impl BatchResult {
+    /// Whether the file could not be run or left processes unfinished
+    pub fn failed(&self) -> bool {
+        match &self.outcome {
+            Ok(summary) => summary.finished < summary.processes,
+            Err(_) => true,
+        }
+    }
+}
+
+/// Finds every scenario in a directory (and the directories below it), or every file matching a glob pattern
+pub fn find_scenarios(pattern: &str) -> std::result::Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = vec![];
+
+    if Path::new(pattern).is_dir() {
+        collect_dir(Path::new(pattern), &mut files)?;
+    }
+    else {
+        for entry in glob::glob(pattern)? {
+            let path = entry?;
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn collect_dir(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect_dir(&path, files)?;
+        }
+        else if path.extension().and_then(|e| e.to_str()).is_some_and(|e| SCENARIO_EXTENSIONS.contains(&e)) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs one scenario and writes its output file the same way a single run does
+pub fn run_file(file: &Path, format: Format) -> BatchResult {
+    let outcome = run_and_write(file, format).map_err(|e| e.to_string());
+
+    BatchResult {
+        label: file.display().to_string(),
+        outcome,
+    }
+}
+
+/// Runs a model that is already built without writing any output, for comparing runs side by side
+pub fn run_model(label: String, mut model: ScheduleModel) -> BatchResult {
+    let mut cpu = CPU::with_sink(Box::new(DiscardSink::default()));
+    cpu.run(&mut model);
+
+    BatchResult {
+        label,
+        outcome: Ok(RunSummary::from_model(&model)),
+    }
+}
+
+fn run_and_write(file: &Path, format: Format) -> std::result::Result<RunSummary, Box<dyn Error>> {
+    let (mut model, output_options) = read_file(&file.to_string_lossy())?;
+
+    // a relative output.path is already next to the scenario, so scenarios in different directories naming the same one do not overwrite each other
+    let output_path = output_options.path.map(PathBuf::from).unwrap_or_else(|| file.with_extension("out"));
+    let file = File::create(&output_path).map_err(|e| format!("could not create {}: {}", output_path.display(), e))?;
+
+    // the text log goes straight to the file as it runs, the other formats need the whole run first
+    if format == Format::Text {
+        let mut cpu = CPU::with_sink(Box::new(WriteSink::new(file)));
+        cpu.run(&mut model);
+        cpu.flush()?;
+    }
+    else {
+        let mut cpu = CPU::default();
+        cpu.run(&mut model);
+
+        let mut out = BufWriter::new(file);
+        write_report(&mut out, format, &cpu, &model)?;
+        out.flush()?;
+    }
+
+    Ok(RunSummary::from_model(&model))
+}
+
+/// Prints one row per run, under a first column with the given heading, followed by a count of the failures
+pub fn write_summary(out: &mut dyn Write, label_heading: &str, results: &[BatchResult]) -> Result<()> {
+    let label_width = results.iter().map(|r| r.label.len()).max().unwrap_or(0).max(label_heading.len());
+
+    writeln!(out, "{:<label_width$}  {:<11} {:>9} {:>8} {:>8} {:>10} {:>8} {:>11} {:>6}  status", label_heading, "scheduler", "processes", "finished", "wait", "turnaround", "response", "preemptions", "slices")?;
+
+    for result in results {
+        let label = &result.label;
+        match &result.outcome {
+            Ok(summary) => {
+                let status = if summary.finished < summary.processes {
+                    format!("{} did not finish", summary.processes - summary.finished)
+                }
+                else {
+                    "ok".to_string()
+                };
+
+                writeln!(
+                    out,
+                    "{:<label_width$}  {:<11} {:>9} {:>8} {:>8.2} {:>10.2} {:>8.2} {:>11} {:>6}  {}",
+                    label, summary.scheduler, summary.processes, summary.finished,
+                    summary.average_wait, summary.average_turnaround, summary.average_response,
+                    summary.preemptions, summary.slices, status
+                )?;
+            },
+            Err(message) => writeln!(out, "{:<label_width$}  {:<11} {:>9} {:>8} {:>8} {:>10} {:>8} {:>11} {:>6}  error: {}", label, "-", "-", "-", "-", "-", "-", "-", "-", message)?,
+        }
+    }
+
+    let failures = results.iter().filter(|r| r.failed()).count();
+    writeln!(out)?;
+    writeln!(out, "{} runs, {} ok, {} failed", results.len(), results.len() - failures, failures)
+}
@@ -0,0 +1,149 @@
+// lower bounds no scheduler can beat for a workload, so a run can be measured against what was possible and not just against other runs
+
+use std::collections::VecDeque;
+use std::io::{Result, Write};
+
+use super::batch::BatchResult;
+use super::process::Process;
+
+/// Lower bounds for a workload on a number of cpus, only looking at arrivals, bursts and `after` lists
+pub struct LowerBounds {
+    pub cores: i32,
+    /// Every tick of work has to fit on the cpus after the processes arrive
+    pub work: i32,
+    /// The longest chain of processes waiting on each other, run back to back as soon as each arrives
+    pub critical_path: i32,
+    /// The soonest each process could finish with a cpu to itself, in the order of the process list
+    pub earliest_finish: Vec<i32>,
+    /// Turnaround is counted from a process's arrival, so every process takes at least the time to its earliest finish
+    pub average_turnaround: f64,
+}
+
+impl LowerBounds {
+    pub fn of(processes: &[Process], cores: i32) -> LowerBounds {
+        let earliest_finish = earliest_finishes(processes);
+        let turnaround: i64 = processes.iter().zip(&earliest_finish).map(|(p, finish)| (finish - p.arrival_time) as i64).sum();
+
+        LowerBounds {
+            cores,
+            work: work_bound(processes, cores),
+            critical_path: earliest_finish.iter().copied().max().unwrap_or_default(),
+            average_turnaround: match processes.len() {
+                0 => 0.0,
+                count => turnaround as f64 / count as f64,
+            },
+            earliest_finish,
+        }
+    }
+
+    /// The soonest the last process could finish
+    pub fn makespan(&self) -> i32 {
+        self.work.max(self.critical_path)
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "lower bounds on {} {}: makespan {} (work {}, critical path {}), average turnaround {:.2}",
+            self.cores, if self.cores == 1 { "cpu" } else { "cpus" },
+            self.makespan(), self.work, self.critical_path, self.average_turnaround
+        )
+    }
+
+    /// The bounds next to what the finished run managed, for the end of its output
+    pub fn report(&self, processes: &[Process]) -> Vec<String> {
+        let mut lines = vec![String::new(), self.describe()];
+
+        match makespan(processes) {
+            Some(makespan) => lines.push(format!("makespan {:3}, {:+} over the bound", makespan, makespan - self.makespan())),
+            None => lines.push("makespan unknown, not every process finished".to_string()),
+        }
+
+        for (process, earliest_finish) in processes.iter().zip(&self.earliest_finish) {
+            match process.finished() {
+                true => lines.push(format!("{} earliest finish {:3}, finished {:3} ({:+})",
+                    process.name, earliest_finish, process.finish_time, process.finish_time - earliest_finish)),
+                false => lines.push(format!("{} earliest finish {:3}, did not finish", process.name, earliest_finish)),
+            }
+        }
+
+        lines
+    }
+}
+
+/// Prints the bounds, then how far over them every run's makespan and average turnaround came, under a first column with the given heading
+pub fn write_gaps(out: &mut dyn Write, bounds: &LowerBounds, label_heading: &str, results: &[BatchResult]) -> Result<()> {
+    let label_width = results.iter().map(|r| r.label.len()).max().unwrap_or(0).max(label_heading.len());
+
+    writeln!(out)?;
+    writeln!(out, "{}", bounds.describe())?;
+    writeln!(out, "{:<label_width$}  {:>8} {:>6} {:>10} {:>8}", label_heading, "makespan", "gap", "turnaround", "gap")?;
+
+    for result in results {
+        // the turnaround of a run that left processes unfinished only covers the ones that did finish, so it is not compared
+        let finished = result.outcome.as_ref().ok().and_then(|summary| summary.makespan.map(|makespan| (summary, makespan)));
+
+        match finished {
+            Some((summary, makespan)) => writeln!(out, "{:<label_width$}  {:>8} {:>+6} {:>10.2} {:>+8.2}",
+                result.label, makespan, makespan - bounds.makespan(),
+                summary.average_turnaround, summary.average_turnaround - bounds.average_turnaround)?,
+            None => writeln!(out, "{:<label_width$}  {:>8} {:>6} {:>10} {:>8}", result.label, "-", "-", "-", "-")?,
+        }
+    }
+
+    Ok(())
+}
+
+/// When the last process finished, if they all did
+pub fn makespan(processes: &[Process]) -> Option<i32> {
+    match processes.iter().all(|p| p.finished()) {
+        true => Some(processes.iter().map(|p| p.finish_time).max().unwrap_or_default()),
+        false => None,
+    }
+}
+
+/// For every arrival time, the work arriving from then on spread over the cpus, the latest of which no schedule can beat
+fn work_bound(processes: &[Process], cores: i32) -> i32 {
+    let mut by_arrival: Vec<&Process> = processes.iter().collect();
+    by_arrival.sort_by_key(|p| std::cmp::Reverse(p.arrival_time));
+
+    let mut work_after = 0i64;
+    let mut bound = 0i64;
+    for process in by_arrival {
+        work_after += process.burst_time as i64;
+        let ticks = (work_after + cores as i64 - 1) / cores as i64;
+        bound = bound.max(process.arrival_time as i64 + ticks);
+    }
+
+    bound.try_into().unwrap_or(i32::MAX)
+}
+
+/// Each process starting once it has arrived and everything it waits on has finished, in topological order
+fn earliest_finishes(processes: &[Process]) -> Vec<i32> {
+    let index_of = |name: &String| processes.iter().position(|p| &p.name == name);
+    let waits_on: Vec<Vec<usize>> = processes.iter().map(|p| p.after.iter().filter_map(index_of).collect()).collect();
+
+    let mut waited_on_by = vec![vec![]; processes.len()];
+    let mut unfinished_dependencies: Vec<usize> = waits_on.iter().map(Vec::len).collect();
+    for (index, dependencies) in waits_on.iter().enumerate() {
+        for &dependency in dependencies {
+            waited_on_by[dependency].push(index);
+        }
+    }
+
+    let mut finishes = vec![0; processes.len()];
+    let mut ready: VecDeque<usize> = (0..processes.len()).filter(|i| unfinished_dependencies[*i] == 0).collect();
+
+    while let Some(index) = ready.pop_front() {
+        let start = waits_on[index].iter().map(|d| finishes[*d]).fold(processes[index].arrival_time, i32::max);
+        finishes[index] = start.saturating_add(processes[index].burst_time).saturating_add(processes[index].io_time());
+
+        for &next in &waited_on_by[index] {
+            unfinished_dependencies[next] -= 1;
+            if unfinished_dependencies[next] == 0 {
+                ready.push_back(next);
+            }
+        }
+    }
+
+    finishes
+}
@@ -0,0 +1,47 @@
+// builds the variants of a scenario that `compare` and `sweep` run side by side
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::ops::RangeInclusive;
+
+use serde_json::Value;
+
+use super::registry::build_scheduler;
+use super::schedule::ScheduleModel;
+
+/// The built in schedulers a scenario is compared across
+const COMPARED: [&str; 5] = ["fcfs", "sjf", "realSJF", "sjf-predict", "rr"];
+
+/// A copy of the model for every built in scheduler, `rr` using the given quantum
+pub fn compare_models(model: &ScheduleModel, quantum: i32) -> Result<Vec<(String, ScheduleModel)>, Box<dyn Error>> {
+    COMPARED.iter()
+        .map(|name| {
+            let mut params = HashMap::new();
+            let mut label = name.to_string();
+            if *name == "rr" {
+                params.insert("quantum".to_string(), Value::from(quantum));
+                label = format!("rr quantum {}", quantum);
+            }
+
+            Ok((label, with_scheduler(model, name, &params)?))
+        })
+        .collect()
+}
+
+/// A copy of the model running `rr` for every quantum in the range
+pub fn sweep_models(model: &ScheduleModel, quantums: RangeInclusive<i32>) -> Result<Vec<(String, ScheduleModel)>, Box<dyn Error>> {
+    quantums
+        .map(|quantum| {
+            let params = HashMap::from([("quantum".to_string(), Value::from(quantum))]);
+            Ok((format!("quantum {}", quantum), with_scheduler(model, "rr", &params)?))
+        })
+        .collect()
+}
+
+/// A copy of the model running the scheduler a `use` line with the given keyword and parameters would pick
+pub fn with_scheduler(model: &ScheduleModel, name: &str, params: &HashMap<String, Value>) -> Result<ScheduleModel, Box<dyn Error>> {
+    let mut variant = model.clone();
+    variant.scheduler = build_scheduler(name, params)?;
+
+    Ok(variant)
+}
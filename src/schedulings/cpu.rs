@@ -1,40 +1,135 @@
-use std::{fs::File, io::{Result, Write}};
+use std::collections::HashSet;
+use std::io::{Result, Write};
 
 use super::{
-    process::Process, 
-    schedule::ScheduleModel, 
-    scheduler::Scheduler, 
+    bounds::LowerBounds,
+    fairness::FairnessLimits,
+    process::{Process, ProcessState},
+    schedule::ScheduleModel,
+    scheduler::Scheduler,
+    sink::{MemorySink, Sink},
 };
 
-#[derive(Default)]
 pub struct CPU {
-    output: Vec<String>,
+    /// Where the log goes as it is written, in memory unless told otherwise
+    pub sink: Box<dyn Sink>,
+    /// Also log preemptions and expired quantums, which the plain output leaves out
+    pub verbose: bool,
+    /// Ends the output with the ready streaks of every process, flagging any that starved
+    pub fairness: Option<FairnessLimits>,
+    /// Ends the output with the lower bounds for the workload on this many cpus, and how far the run came from them
+    pub bounds: Option<i32>,
+}
+
+impl Default for CPU {
+    fn default() -> CPU {
+        CPU::with_sink(Box::new(MemorySink::default()))
+    }
 }
 
 impl CPU {
-    pub fn run(&mut self, mut model: ScheduleModel) {
-        let scheduler = model.scheduler.as_mut();
+    /// A cpu that has already written the given lines, used when picking a run back up
+    pub fn with_output(output: Vec<String>) -> CPU {
+        CPU::with_sink(Box::new(MemorySink::from(output)))
+    }
+
+    /// A cpu that writes its log to the given sink as it goes
+    pub fn with_sink(sink: Box<dyn Sink>) -> CPU {
+        CPU { sink, verbose: false, fairness: None, bounds: None }
+    }
+
+    pub fn run(&mut self, model: &mut ScheduleModel) {
+        self.start(model);
+        self.resume(model, 0);
+    }
 
-        self.output.push(format!("{:3} processes", model.number_of_processes));
-        self.output.push(format!("Using {}", scheduler.descriptive_name()));
+    /// Runs the model the same way `run` does, calling `after_tick` with the cpu, the model and the time once each tick is done
+    pub fn run_with(&mut self, model: &mut ScheduleModel, mut after_tick: impl FnMut(&CPU, &ScheduleModel, i32)) {
+        self.start(model);
 
         for t in 0..model.time_units {
-            self.tick_proceses(&mut model.process_list, t);
-            self.handle_arrivals(&mut model.process_list, scheduler, t);
-            self.handle_on_tick(&mut model.process_list, scheduler, t);
-            self.handle_finishes(&mut model.process_list, scheduler, t);
-            self.handle_pre_tick(&mut model.process_list, scheduler, t);
+            self.step(model, t);
+            after_tick(self, model, t);
+        }
+
+        self.end(model);
+    }
 
-            self.handle_selection_output(&model.process_list, scheduler, t)
+    /// Runs every tick from the given time to the end of the model and writes the summary
+    pub fn resume(&mut self, model: &mut ScheduleModel, from: i32) {
+        for t in from..model.time_units {
+            self.step(model, t);
         }
 
-        self.output.push(format!("Finished at time {:3}", model.time_units));
-        self.output.push(format!(""));
+        self.end(model);
+    }
+
+    /// Writes the header lines for the given model
+    pub fn start(&mut self, model: &ScheduleModel) {
+        self.sink.write_line(format_args!("{:3} processes", model.number_of_processes));
+        self.sink.write_line(format_args!("Using {}", model.scheduler.descriptive_name()));
+    }
+
+    /// Runs every phase of a single tick at the given time
+    pub fn step(&mut self, model: &mut ScheduleModel, t: i32) {
+        let scheduler = model.scheduler.as_mut();
+
+        self.tick_proceses(&mut model.process_list, t);
+        self.handle_arrivals(&mut model.process_list, scheduler, t);
+        self.handle_burst_ends(&mut model.process_list, scheduler, t);
+        self.handle_io_ends(&mut model.process_list, scheduler, t);
+        self.handle_on_tick(&mut model.process_list, scheduler, t);
+        self.handle_finishes(&mut model.process_list, scheduler, t);
+        self.handle_releases(&mut model.process_list, scheduler, t);
+        self.handle_pre_tick(&mut model.process_list, scheduler, t);
+
+        self.handle_selection_output(&model.process_list, scheduler, t)
+    }
+
+    /// Writes the finish time and the per process summary
+    pub fn end(&mut self, model: &ScheduleModel) {
+        self.sink.write_line(format_args!("Finished at time {:3}", model.time_units));
+        self.sink.write_line(format_args!(""));
 
         self.handle_status_output(&model.process_list);
+        self.write_lines(model.scheduler.summary(&model.process_list));
+
+        if let Some(limits) = &self.fairness {
+            self.write_lines(limits.report(&model.process_list));
+        }
+
+        if let Some(cores) = self.bounds {
+            self.write_lines(LowerBounds::of(&model.process_list, cores).report(&model.process_list));
+        }
+    }
+
+    /// The lines written so far, only kept when writing to memory
+    pub fn output(&self) -> &[String] {
+        self.sink.lines()
     }
 
-    fn tick_proceses(&mut self, processes: &mut Vec<Process>, cur_time: i32) {
+    /// Whether `output` has every line written so far, rather than them going somewhere else
+    pub fn keeps_output(&self) -> bool {
+        self.sink.keeps_lines()
+    }
+
+    /// How many lines have been written so far, wherever they went
+    pub fn line_count(&self) -> usize {
+        self.sink.line_count()
+    }
+
+    /// Writes out whatever the sink still has buffered, returning the first error it ran into
+    pub fn flush(&mut self) -> Result<()> {
+        self.sink.flush()
+    }
+
+    fn write_lines(&mut self, lines: Vec<String>) {
+        for line in lines {
+            self.sink.write_line(format_args!("{}", line));
+        }
+    }
+
+    fn tick_proceses(&mut self, processes: &mut [Process], cur_time: i32) {
         processes.iter_mut()
         .filter(|p| p.arrived(cur_time - 1))
         .for_each(|p| {
@@ -43,75 +138,149 @@ impl CPU {
     }
 
 
-    fn handle_on_tick(&mut self, processes: &mut Vec<Process>, scheduler: &mut dyn Scheduler, cur_time: i32) {
+    fn handle_on_tick(&mut self, processes: &mut [Process], scheduler: &mut dyn Scheduler, cur_time: i32) {
         processes.iter_mut()
-        .filter(|p| !p.finished() && p.arrived(cur_time - 1))
-        .for_each(|p| scheduler.on_tick(p, cur_time));
+        .filter(|p| !p.finished() && !p.in_io() && p.released_by(cur_time - 1))
+        .for_each(|p| {
+            let was_running = p.state == Some(ProcessState::Running);
+            scheduler.on_tick(p, cur_time);
+
+            // the only reason to take a process off the cpu while just ticking is the end of its time slice
+            if self.verbose && was_running && p.state == Some(ProcessState::Ready) {
+                self.sink.write_line(format_args!("Time {:3} : {} quantum expired", cur_time, p.name));
+            }
+        });
     }
 
-    fn handle_finishes(&mut self, processes: &mut Vec<Process>, scheduler: &mut dyn Scheduler, cur_time: i32) {
+    fn handle_finishes(&mut self, processes: &mut [Process], scheduler: &mut dyn Scheduler, cur_time: i32) {
         processes.iter_mut()
         .filter(|p| p.finished() && p.finish_time == cur_time)
         .for_each(|p| {
-            self.output.push(format!("Time {:3} : {} finished", cur_time, p.name));
+            self.sink.write_line(format_args!("Time {:3} : {} finished", cur_time, p.name));
             scheduler.on_finish(p, cur_time);
         });
     }
 
-    fn handle_arrivals(&mut self, processes: &mut Vec<Process>, scheduler: &mut dyn Scheduler, cur_time: i32) {
+    fn handle_burst_ends(&mut self, processes: &mut [Process], scheduler: &mut dyn Scheduler, cur_time: i32) {
+        processes.iter_mut()
+        .filter(|p| p.ended_burst(cur_time))
+        .for_each(|p| {
+            self.sink.write_line(format_args!("Time {:3} : {} finished burst {} of {}", cur_time, p.name, p.burst_index, p.bursts.len()));
+
+            // a process with I/O to do leaves the scheduler until it is ready again
+            match p.in_io() {
+                true => scheduler.on_finish(p, cur_time),
+                false => scheduler.on_burst_end(p, cur_time),
+            }
+        });
+    }
+
+    fn handle_io_ends(&mut self, processes: &mut [Process], scheduler: &mut dyn Scheduler, cur_time: i32) {
+        processes.iter_mut()
+        .filter(|p| p.ended_io(cur_time))
+        .for_each(|p| {
+            p.deselect();
+            self.sink.write_line(format_args!("Time {:3} : {} finished I/O", cur_time, p.name));
+            scheduler.on_arrive(p, cur_time);
+        });
+    }
+
+    fn handle_arrivals(&mut self, processes: &mut [Process], scheduler: &mut dyn Scheduler, cur_time: i32) {
         // goes through all the processes inputted in and sees if the current time in the scheduling matches any arrival times
         for process in processes.iter_mut() {
             if process.arrival_time == cur_time {
-                process.deselect(); // sets the process as ready when arriving
-                self.output.push(format!("Time {:3} : {} arrived", cur_time, process.name));
-                scheduler.on_arrive(process, cur_time);
+                self.sink.write_line(format_args!("Time {:3} : {} arrived", cur_time, process.name));
+
+                // a process waiting on others is held back until handle_releases
+                if process.after.is_empty() {
+                    process.deselect(); // sets the process as ready when arriving
+                    scheduler.on_arrive(process, cur_time);
+                }
             }
         }
     }
 
-    fn handle_pre_tick(&mut self, processes: &mut Vec<Process>, scheduler: &mut dyn Scheduler, cur_time: i32) {
+    fn handle_releases(&mut self, processes: &mut [Process], scheduler: &mut dyn Scheduler, cur_time: i32) {
+        let held: Vec<usize> = processes.iter().enumerate()
+            .filter(|(_, p)| !p.after.is_empty() && p.release_time.is_none() && p.arrived(cur_time))
+            .map(|(index, _)| index)
+            .collect();
+
+        // without dependencies nothing is ever held back, so there is nothing more to look at
+        if held.is_empty() {
+            return;
+        }
+
+        let finished: HashSet<&str> = processes.iter()
+            .filter(|p| p.finished())
+            .map(|p| p.name.as_str())
+            .collect();
+        let released: Vec<usize> = held.into_iter()
+            .filter(|index| processes[*index].after.iter().all(|name| finished.contains(name.as_str())))
+            .collect();
+
+        for index in released {
+            let p = &mut processes[index];
+            p.release(cur_time);
+            self.sink.write_line(format_args!("Time {:3} : {} released", cur_time, p.name));
+            scheduler.on_arrive(p, cur_time);
+        }
+    }
+
+    fn handle_pre_tick(&mut self, processes: &mut [Process], scheduler: &mut dyn Scheduler, cur_time: i32) {
+        let mut preempted = vec![];
+
         processes.iter_mut()
-        .filter(|p| !p.finished() && p.arrived(cur_time))
+        .filter(|p| !p.finished() && !p.in_io() && p.released_by(cur_time))
         .for_each(|p| {
+            let was_running = p.state == Some(ProcessState::Running);
             scheduler.pre_tick(p, cur_time);
+
+            if was_running && p.state == Some(ProcessState::Ready) {
+                preempted.push((p.name.to_owned(), p.burst_remaining()));
+            }
         });
+
+        if self.verbose {
+            let by = scheduler.selected_process_name().unwrap_or_default();
+            for (name, remaining) in preempted {
+                self.sink.write_line(format_args!("Time {:3} : {} preempted by {} (remaining {})", cur_time, name, by, remaining));
+            }
+        }
     }
 
-    fn handle_selection_output(&mut self, processes: &Vec<Process>, scheduler: &dyn Scheduler, cur_time: i32) {    
+    fn handle_selection_output(&mut self, processes: &[Process], scheduler: &dyn Scheduler, cur_time: i32) {
         // if we have a possible new selection at the end of the cur_time
         for process in processes {
             if let Some(last_selection_time) = process.last_selection_time {
                 if last_selection_time == cur_time {
-                    self.output.push(format!("Time {:3} : {} selected (burst {:3})", cur_time, process.name, process.time_remaining))
+                    self.sink.write_line(format_args!("Time {:3} : {} selected (burst {:3})", cur_time, process.name, process.burst_remaining()))
                 }
             }
         }
 
         if scheduler.selected_process_name().is_none() {
-            self.output.push(format!("Time {:3} : Idle", cur_time));
+            self.sink.write_line(format_args!("Time {:3} : Idle", cur_time));
         }
     }
 
-    fn handle_status_output(&mut self, processes: &Vec<Process>) {
+    fn handle_status_output(&mut self, processes: &[Process]) {
         for process in processes.iter() {
             if process.finished() {
-                let line = format!("{} wait {:3} turnaround {:3} response {:3}", process.name, process.wait_time, process.turnaround_time, process.response_time);
-                self.output.push(line);
+                self.sink.write_line(format_args!("{} wait {:3} turnaround {:3} response {:3}", process.name, process.wait_time, process.turnaround_time, process.response_time));
             }
             else {
-                self.output.push(format!("{} did not finish", process.name));
+                self.sink.write_line(format_args!("{} did not finish", process.name));
             }
         }
     }
 
-    pub fn write_output_file(&self, file_path: &str) -> Result<()>{
-        let mut file = File::create(file_path)?;
-
-        for line in self.output.iter() {
-            file.write((line.to_owned() + "\n").as_bytes())?;
+    pub fn write_output(&self, out: &mut dyn Write) -> Result<()>{
+        for line in self.output() {
+            out.write_all((line.to_owned() + "\n").as_bytes())?;
         }
 
         Ok(())
     }
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,442 @@
+// a scheduler scripted in the scenario itself, picking the process with the lowest score from an expression over its fields
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::process::{Process, ProcessState};
+use super::registry::{restore_as, Registration};
+use super::scheduler::Scheduler;
+
+/// A value the score expression can read, the long names are the `Process` fields
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum Var {
+    /// What is left of the current burst
+    Remaining,
+    /// What is left of every burst
+    TimeRemaining,
+    Burst,
+    Wait,
+    Arrival,
+    /// How many ticks in a row the process has been waiting
+    Streak,
+    Preemptions,
+    /// The time of the decision
+    Time,
+}
+
+impl Var {
+    const NAMES: [(&'static str, Var); 13] = [
+        ("remaining", Var::Remaining),
+        ("time_remaining", Var::TimeRemaining),
+        ("burst", Var::Burst),
+        ("burst_time", Var::Burst),
+        ("wait", Var::Wait),
+        ("wait_time", Var::Wait),
+        ("arrival", Var::Arrival),
+        ("arrival_time", Var::Arrival),
+        ("streak", Var::Streak),
+        ("ready_streak", Var::Streak),
+        ("preemptions", Var::Preemptions),
+        ("time", Var::Time),
+        ("now", Var::Time),
+    ];
+
+    fn named(name: &str) -> Option<Var> {
+        Var::NAMES.iter().find(|(n, _)| *n == name).map(|(_, var)| *var)
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub enum Func {
+    Min,
+    Max,
+    Abs,
+}
+
+/// The longest score that can be parsed
+const MAX_TOKENS: usize = 256;
+
+/// A parsed score expression
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Expr {
+    Number(f64),
+    Var(Var),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+    Call(Func, Vec<Expr>),
+}
+
+/// The fields of a process as of its last tick, which is all the expression can see
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Fields {
+    remaining: i32,
+    time_remaining: i32,
+    burst: i32,
+    wait: i32,
+    arrival: i32,
+    streak: i32,
+    preemptions: i32,
+}
+
+impl Fields {
+    pub fn of(process: &Process) -> Fields {
+        Fields {
+            remaining: process.burst_remaining(),
+            time_remaining: process.time_remaining,
+            burst: process.burst_time,
+            wait: process.wait_time,
+            arrival: process.arrival_time,
+            streak: process.ready_streak,
+            preemptions: process.preemptions,
+        }
+    }
+
+    fn get(&self, var: Var, time: i32) -> f64 {
+        let value = match var {
+            Var::Remaining => self.remaining,
+            Var::TimeRemaining => self.time_remaining,
+            Var::Burst => self.burst,
+            Var::Wait => self.wait,
+            Var::Arrival => self.arrival,
+            Var::Streak => self.streak,
+            Var::Preemptions => self.preemptions,
+            Var::Time => time,
+        };
+
+        value as f64
+    }
+}
+
+impl Expr {
+    /// Parses an expression of numbers, process fields, `+ - * /`, brackets and `min(a, b)`, `max(a, b)` or `abs(a)`
+    pub fn parse(source: &str) -> Result<Expr, Box<dyn Error>> {
+        let tokens = lex(source)?;
+
+        // parsing, scoring and dropping the expression all recurse as deep as it nests, so it is kept well short of the stack
+        if tokens.len() > MAX_TOKENS {
+            return Err(format!("a score can have at most {} numbers, names and symbols", MAX_TOKENS).into());
+        }
+        let mut parser = Parser { tokens: &tokens, next: 0 };
+
+        let expr = parser.sum()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected `{}` in score", token).into());
+        }
+
+        Ok(expr)
+    }
+
+    pub fn eval(&self, fields: &Fields, time: i32) -> f64 {
+        match self {
+            Expr::Number(number) => *number,
+            Expr::Var(var) => fields.get(*var, time),
+            Expr::Neg(inner) => -inner.eval(fields, time),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.eval(fields, time), right.eval(fields, time));
+                match op {
+                    Op::Add => left + right,
+                    Op::Sub => left - right,
+                    Op::Mul => left * right,
+                    Op::Div => left / right,
+                }
+            },
+            Expr::Call(func, args) => {
+                let mut args = args.iter().map(|arg| arg.eval(fields, time));
+                match func {
+                    Func::Min => args.fold(f64::INFINITY, f64::min),
+                    Func::Max => args.fold(f64::NEG_INFINITY, f64::max),
+                    Func::Abs => args.next().unwrap_or_default().abs(),
+                }
+            },
+        }
+    }
+}
+
+fn lex(source: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut tokens = vec![];
+    let mut chars = source.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        }
+        else if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric() || **c == '_' || **c == '.') {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(word);
+        }
+        else if "+-*/(),".contains(c) {
+            tokens.push(c.to_string());
+            chars.next();
+        }
+        else {
+            return Err(format!("unexpected `{}` in score", c).into());
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser, one method per level of precedence
+struct Parser<'a> {
+    tokens: &'a [String],
+    next: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.next).map(String::as_str)
+    }
+
+    fn take(&mut self) -> Option<&str> {
+        self.next += 1;
+        self.tokens.get(self.next - 1).map(String::as_str)
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), Box<dyn Error>> {
+        match self.take() {
+            Some(found) if found == token => Ok(()),
+            Some(found) => Err(format!("expected `{}` in score, found `{}`", token, found).into()),
+            None => Err(format!("expected `{}` at the end of the score", token).into()),
+        }
+    }
+
+    fn sum(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let mut expr = self.product()?;
+        while let Some(op) = self.peek().and_then(|token| match token {
+            "+" => Some(Op::Add),
+            "-" => Some(Op::Sub),
+            _ => None,
+        }) {
+            self.next += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.product()?));
+        }
+
+        Ok(expr)
+    }
+
+    fn product(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let mut expr = self.unary()?;
+        while let Some(op) = self.peek().and_then(|token| match token {
+            "*" => Some(Op::Mul),
+            "/" => Some(Op::Div),
+            _ => None,
+        }) {
+            self.next += 1;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.unary()?));
+        }
+
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Box<dyn Error>> {
+        if self.peek() == Some("-") {
+            self.next += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let token = self.take().ok_or("the score ends too early")?.to_string();
+
+        if token == "(" {
+            let expr = self.sum()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        if let Ok(number) = token.parse::<f64>() {
+            return Ok(Expr::Number(number));
+        }
+        if let Some(var) = Var::named(&token) {
+            return Ok(Expr::Var(var));
+        }
+
+        let (func, takes): (Func, fn(usize) -> bool) = match token.as_str() {
+            "min" => (Func::Min, |count| count >= 2),
+            "max" => (Func::Max, |count| count >= 2),
+            "abs" => (Func::Abs, |count| count == 1),
+            _ => return Err(format!("unknown name `{}` in score", token).into()),
+        };
+
+        self.expect("(")?;
+        let mut args = vec![self.sum()?];
+        while self.peek() == Some(",") {
+            self.next += 1;
+            args.push(self.sum()?);
+        }
+        self.expect(")")?;
+
+        if !takes(args.len()) {
+            return Err(format!("`{}` is given the wrong number of values", token).into());
+        }
+
+        Ok(Expr::Call(func, args))
+    }
+}
+
+/// Runs the process with the lowest score, at every tick when preemptive or whenever the cpu frees up when not
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Custom {
+    /// The expression as written, for the output header
+    source: String,
+    score: Expr,
+    preemptive: bool,
+
+    /// The fields of every waiting or running process
+    jobs: HashMap<String, Fields>,
+    selected_process: Option<String>,
+    /// The last time a process was picked, so it only happens once a tick
+    picked_at: Option<i32>,
+}
+
+impl Custom {
+    /// How `use custom score = ...` builds the scheduler, a trailing `preemptive` on the score is the same as `preemptive true`
+    pub fn registration() -> Registration {
+        Registration {
+            keyword: "custom",
+            description: "runs the process with the lowest `score`, an expression such as `remaining + wait / 4`, optionally `preemptive`",
+            params: &["score", "preemptive"],
+            build: |params| Ok(Box::new(Custom::from_params(params)?)),
+            restore: restore_as::<Custom>,
+        }
+    }
+
+    pub fn new(source: &str, preemptive: bool) -> Result<Custom, Box<dyn Error>> {
+        Ok(Custom {
+            source: source.to_string(),
+            score: Expr::parse(source)?,
+            preemptive,
+            jobs: HashMap::new(),
+            selected_process: None,
+            picked_at: None,
+        })
+    }
+
+    fn from_params(params: &HashMap<String, Value>) -> Result<Custom, Box<dyn Error>> {
+        let source = params.get("score").and_then(Value::as_str).ok_or("custom needs a `score` expression")?;
+
+        let (source, trailing) = match source.trim_end().strip_suffix("preemptive") {
+            Some(rest) if rest.ends_with(char::is_whitespace) => (rest.trim_end(), true),
+            _ => (source, false),
+        };
+        let preemptive = match params.get("preemptive") {
+            None => trailing,
+            Some(Value::Bool(flag)) => *flag,
+            Some(Value::String(flag)) if flag == "true" || flag == "false" => flag == "true",
+            Some(value) => return Err(format!("`preemptive` must be true or false, found {}", value).into()),
+        };
+
+        Custom::new(source, preemptive)
+    }
+
+    /// Every job by score at the given time, lowest first, with ties going to the earliest arrival
+    fn ranked(&self, time: i32) -> Vec<(f64, &String)> {
+        let mut ranked: Vec<(f64, &String, i32)> = self.jobs.iter()
+            .map(|(name, fields)| (self.score.eval(fields, time), name, fields.arrival))
+            .collect();
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.2.cmp(&b.2)).then(a.1.cmp(b.1)));
+
+        ranked.into_iter().map(|(score, name, _)| (score, name)).collect()
+    }
+
+    fn pick(&mut self, time: i32) {
+        if self.selected_process.is_some() && !self.preemptive {
+            return;
+        }
+
+        let ranked = self.ranked(time);
+        let Some(&(best_score, best)) = ranked.first() else {
+            self.selected_process = None;
+            return;
+        };
+
+        // the running process keeps the cpu on a tie
+        let running = ranked.iter().find(|(_, name)| Some(*name) == self.selected_process.as_ref());
+        if running.is_some_and(|(score, _)| *score <= best_score) {
+            return;
+        }
+
+        self.selected_process = Some(best.to_owned());
+    }
+}
+
+impl Scheduler for Custom {
+    fn descriptive_name(&self) -> String {
+        match self.preemptive {
+            true => format!("custom score = {} (preemptive)", self.source),
+            false => format!("custom score = {}", self.source),
+        }
+    }
+
+    fn on_arrive(&mut self, process: &mut Process, _: i32) {
+        self.jobs.insert(process.name.to_owned(), Fields::of(process));
+    }
+
+    fn pre_tick(&mut self, process: &mut Process, time: i32) {
+        if self.picked_at != Some(time) {
+            self.picked_at = Some(time);
+            self.pick(time);
+        }
+
+        if self.selected_process.as_ref() == Some(&process.name) {
+            if process.state != Some(ProcessState::Running) {
+                process.select(time);
+            }
+        }
+        else {
+            process.deselect();
+        }
+    }
+
+    fn on_tick(&mut self, process: &mut Process, _: i32) {
+        if let Some(fields) = self.jobs.get_mut(&process.name) {
+            *fields = Fields::of(process);
+        }
+    }
+
+    fn on_finish(&mut self, process: &Process, _: i32) {
+        self.jobs.remove(&process.name);
+        if self.selected_process.as_ref() == Some(&process.name) {
+            self.selected_process = None;
+        }
+    }
+
+    fn selected_process_name(&self) -> Option<String> {
+        self.selected_process.clone()
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        self.ranked(self.picked_at.unwrap_or_default()).into_iter()
+            .filter(|(_, name)| Some(*name) != self.selected_process.as_ref())
+            .map(|(_, name)| name.to_owned())
+            .collect()
+    }
+
+    fn keyword(&self) -> String {
+        "custom".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<Value> {
+        serde_json::to_value(self)
+    }
+}
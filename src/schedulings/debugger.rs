@@ -0,0 +1,252 @@
+use std::io::{self, BufRead, BufReader, Error, ErrorKind, Result, Write};
+
+use super::{
+    cpu::CPU,
+    process::Process,
+    schedule::ScheduleModel,
+    sink::MemorySink,
+    snapshot::Checkpoint,
+    timeline::Keyframes,
+};
+
+/// How far the debugger lets the simulation run before pausing again
+enum RunMode {
+    /// Pause once this many more ticks have run
+    Step(i32),
+    /// Pause after the next tick that logs anything other than `Idle`
+    NextEvent,
+    /// Pause once the simulation reaches the given time
+    Until(i32),
+    /// Only pause on a breakpoint
+    Continue,
+}
+
+const HELP: &str = "\
+commands:
+  s, step [N]     run N ticks (default 1), an empty line steps once
+  n, next         run until the next tick that logs an event
+  u, until T      run until time T
+  back [N]        rewind N ticks (default 1)
+  b, break NAME   pause whenever NAME is selected
+  d, delete NAME  remove the breakpoint on NAME
+  c, continue     run until a breakpoint or the end
+  p, print        show the current state again
+  save PATH       write a checkpoint of the current tick to PATH
+  q, quit         finish the run without pausing
+  h, help         show this message";
+
+/// Interactive step-through of a simulation that pauses between ticks to show the cpu state
+pub struct Debugger {
+    input: Box<dyn BufRead>,
+    out: Box<dyn Write>,
+    mode: RunMode,
+    breakpoints: Vec<String>,
+}
+
+impl Debugger {
+    pub fn new(input: Box<dyn BufRead>, out: Box<dyn Write>) -> Debugger {
+        Debugger {
+            input,
+            out,
+            mode: RunMode::Step(1),
+            breakpoints: vec![],
+        }
+    }
+
+    /// A debugger reading commands from stdin and printing to stdout
+    pub fn stdio() -> Debugger {
+        Debugger::new(Box::new(BufReader::new(io::stdin())), Box::new(io::stdout()))
+    }
+
+    /// Runs the model on the cpu the same way `CPU::run` does, pausing for commands between ticks
+    pub fn run(&mut self, cpu: &mut CPU, model: &mut ScheduleModel) -> Result<()> {
+        // pausing, breakpoints and rewinding all read back the lines each tick wrote
+        if !cpu.keeps_output() {
+            return Err(Error::new(ErrorKind::InvalidInput, "the debugger needs a cpu that keeps its log in memory"));
+        }
+
+        cpu.start(model);
+        writeln!(self.out, "{}", HELP)?;
+
+        // where every tick's lines start and end, and copies of the model to replay any tick from, so `back` can rewind
+        let mut history: Vec<(usize, usize)> = vec![];
+        let mut keyframes = Keyframes::default();
+        let mut t = 0;
+
+        while t < model.time_units {
+            let written = cpu.output().len();
+            cpu.step(model, t);
+            history.push((written, cpu.output().len()));
+            keyframes.record(model, t);
+
+            if !self.should_pause(&model.process_list, &cpu.output()[written..], t) {
+                t += 1;
+                continue;
+            }
+
+            let mut written = written;
+            loop {
+                let new_lines = cpu.output()[written..].to_vec();
+                self.show(model, &new_lines, t)?;
+
+                let Some(ticks) = self.prompt(cpu, model, &new_lines, t)? else {
+                    break;
+                };
+
+                // rewinding throws away everything after the tick we land on
+                t = (t - ticks).max(0);
+                history.truncate(t as usize + 1);
+                keyframes.truncate(t);
+                *model = keyframes.model_at(t);
+
+                let lines;
+                (written, lines) = history[t as usize];
+                cpu.sink = Box::new(MemorySink::from(cpu.output()[..lines].to_vec()));
+            }
+
+            t += 1;
+        }
+
+        cpu.end(model);
+        Ok(())
+    }
+
+    fn should_pause(&mut self, processes: &[Process], new_lines: &[String], t: i32) -> bool {
+        let hit_breakpoint = processes.iter()
+            .any(|p| p.last_selection_time == Some(t) && self.breakpoints.contains(&p.name));
+
+        let reached = match &mut self.mode {
+            RunMode::Step(remaining) => {
+                *remaining -= 1;
+                *remaining <= 0
+            },
+            RunMode::NextEvent => new_lines.iter().any(|line| !line.ends_with(": Idle")),
+            RunMode::Until(time) => t >= *time,
+            RunMode::Continue => false,
+        };
+
+        reached || hit_breakpoint
+    }
+
+    /// Reads commands until one of them resumes the simulation, returning how many ticks to rewind if asked to
+    fn prompt(&mut self, cpu: &CPU, model: &ScheduleModel, new_lines: &[String], t: i32) -> Result<Option<i32>> {
+        loop {
+            write!(self.out, "(step) ")?;
+            self.out.flush()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                // nobody left to answer, so finish the run
+                self.breakpoints.clear();
+                self.mode = RunMode::Continue;
+                return Ok(None);
+            }
+
+            let mut words = line.split_whitespace();
+            let command = words.next();
+            let argument = words.next();
+
+            match command {
+                None => {
+                    self.mode = RunMode::Step(1);
+                    return Ok(None);
+                },
+                Some("s" | "step") => match argument.map(|a| a.parse::<i32>()) {
+                    None => {
+                        self.mode = RunMode::Step(1);
+                        return Ok(None);
+                    },
+                    Some(Ok(count)) if count > 0 => {
+                        self.mode = RunMode::Step(count);
+                        return Ok(None);
+                    },
+                    Some(_) => writeln!(self.out, "step needs a positive number of ticks")?,
+                },
+                Some("n" | "next") => {
+                    self.mode = RunMode::NextEvent;
+                    return Ok(None);
+                },
+                Some("u" | "until") => match argument.map(|a| a.parse::<i32>()) {
+                    Some(Ok(time)) if time > t => {
+                        self.mode = RunMode::Until(time);
+                        return Ok(None);
+                    },
+                    _ => writeln!(self.out, "until needs a time after {}", t)?,
+                },
+                Some("b" | "break") => match argument {
+                    Some(name) if model.process_list.iter().any(|p| p.name == name) => {
+                        if !self.breakpoints.iter().any(|b| b == name) {
+                            self.breakpoints.push(name.to_string());
+                        }
+                        writeln!(self.out, "breaking when {} is selected", name)?;
+                    },
+                    Some(name) => writeln!(self.out, "no process named {}", name)?,
+                    None => writeln!(self.out, "break needs a process name")?,
+                },
+                Some("d" | "delete") => match argument {
+                    Some(name) => {
+                        self.breakpoints.retain(|b| b != name);
+                        writeln!(self.out, "no longer breaking on {}", name)?;
+                    },
+                    None => writeln!(self.out, "delete needs a process name")?,
+                },
+                Some("c" | "continue") => {
+                    self.mode = RunMode::Continue;
+                    return Ok(None);
+                },
+                Some("back") => match argument.map(|a| a.parse::<i32>()) {
+                    _ if t == 0 => writeln!(self.out, "already at the first tick")?,
+                    None => return Ok(Some(1)),
+                    Some(Ok(count)) if count > 0 => return Ok(Some(count)),
+                    Some(_) => writeln!(self.out, "back needs a positive number of ticks")?,
+                },
+                Some("p" | "print") => self.show(model, new_lines, t)?,
+                Some("save") => match argument {
+                    Some(path) => match Checkpoint::capture(cpu, model, t).map_err(|e| e.into()).and_then(|c| c.save(path)) {
+                        Ok(()) => writeln!(self.out, "saved time {} to {}", t, path)?,
+                        Err(e) => writeln!(self.out, "could not save checkpoint: {}", e)?,
+                    },
+                    None => writeln!(self.out, "save needs a file path")?,
+                },
+                Some("q" | "quit") => {
+                    self.breakpoints.clear();
+                    self.mode = RunMode::Continue;
+                    return Ok(None);
+                },
+                Some("h" | "help") => writeln!(self.out, "{}", HELP)?,
+                Some(other) => writeln!(self.out, "unknown command `{}`, try `help`", other)?,
+            }
+        }
+    }
+
+    /// Prints the lines logged this tick, the running process, the ready queue and every process's counters
+    fn show(&mut self, model: &ScheduleModel, new_lines: &[String], t: i32) -> Result<()> {
+        let scheduler = model.scheduler.as_ref();
+
+        writeln!(self.out)?;
+        writeln!(self.out, "=== time {:3} ===", t)?;
+        for line in new_lines {
+            writeln!(self.out, "  {}", line)?;
+        }
+
+        match scheduler.selected_process_name() {
+            Some(name) => writeln!(self.out, "running: {}", name)?,
+            None => writeln!(self.out, "running: (idle)")?,
+        }
+
+        let ready_queue = scheduler.ready_queue();
+        if ready_queue.is_empty() {
+            writeln!(self.out, "ready:   (empty)")?;
+        }
+        else {
+            writeln!(self.out, "ready:   {}", ready_queue.join(" "))?;
+        }
+
+        writeln!(self.out, "{:<10} {:<11} {:>9} {:>5}", "process", "state", "remaining", "wait")?;
+        for process in model.process_list.iter() {
+            writeln!(self.out, "{:<10} {:<11} {:>9} {:>5}", process.name, process.state_name(t), process.time_remaining, process.wait_time)?;
+        }
+
+        Ok(())
+    }
+}
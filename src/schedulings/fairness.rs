@@ -0,0 +1,44 @@
+// flags processes that waited far longer than they should have, the `did not finish` line only catches the worst cases
+
+use super::process::Process;
+
+/// When a process counts as starved
+#[derive(Clone)]
+pub struct FairnessLimits {
+    /// Flag a process that waited more than this many times its burst
+    pub wait_ratio: f64,
+    /// Flag a process that sat in the ready queue for more than this many ticks in a row
+    pub max_streak: i32,
+}
+
+impl FairnessLimits {
+    /// The longest ready streak of every process, with the reasons any of them count as starved
+    pub fn report(&self, processes: &[Process]) -> Vec<String> {
+        let mut lines = vec![
+            String::new(),
+            format!("Fairness (flagging a wait over {}x the burst or over {} ticks ready in a row)", self.wait_ratio, self.max_streak),
+        ];
+        let mut starved = 0;
+
+        for process in processes {
+            let mut reasons = vec![];
+            if process.wait_time as f64 > self.wait_ratio * process.burst_time as f64 {
+                reasons.push(format!("waited {} ticks for a burst of {}", process.wait_time, process.burst_time));
+            }
+            if process.max_ready_streak > self.max_streak {
+                reasons.push(format!("ready {} ticks in a row", process.max_ready_streak));
+            }
+
+            let mut line = format!("{} longest ready streak {:3}", process.name, process.max_ready_streak);
+            if !reasons.is_empty() {
+                line += &format!(" starved: {}", reasons.join(", "));
+                starved += 1;
+            }
+            lines.push(line);
+        }
+
+        lines.push(format!("{} of {} processes starved", starved, processes.len()));
+
+        lines
+    }
+}
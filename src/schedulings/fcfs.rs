@@ -1,34 +1,38 @@
 
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
+use super::registry::{restore_as, Registration};
 use super::scheduler::Scheduler;
 use super::process::{Process, ProcessState};
 
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 
 pub struct FCFS {
-    /// A hashmap that contains keys that represent the process name and a integer for arrival time
-    pub job_list: HashMap<String, i32>,
+    /// A hashmap that contains keys that represent the process name and the time it joined the queue with its arrival time
+    pub job_list: HashMap<String, (i32, i32)>,
     pub first_job: Option<String>
 }
 
 impl FCFS {
-    fn find_first_job(&mut self) {
-        let mut first = ("", &i32::MAX);
-
-        for (name, arrival_time) in self.job_list.iter() {
-            if first.1 > arrival_time {
-                first = (name, arrival_time)
-            }
+    /// How `use fcfs` builds the scheduler
+    pub fn registration() -> Registration {
+        Registration {
+            keyword: "fcfs",
+            description: "First-Come First-Served, runs each process to the end in arrival order",
+            params: &[],
+            build: |_| Ok(Box::new(FCFS::default())),
+            restore: restore_as::<FCFS>,
         }
+    }
 
-        if first.0 == "" {
-            self.first_job = None;
-        }
-        else {
-            self.first_job = Some(first.0.to_string())
-        }
+    /// Picks the process that has been in the queue longest, ties going to the earliest arrival and then the name like in `ready_queue`
+    fn find_first_job(&mut self) {
+        self.first_job = self.job_list.iter()
+            .min_by_key(|(name, joined)| (**joined, *name))
+            .map(|(name, _)| name.to_owned());
     }
 }
 
@@ -40,20 +44,15 @@ impl Scheduler for FCFS {
 
     fn on_arrive(&mut self, process: &mut super::process::Process, time: i32) {
         
-        self.job_list.insert(process.name.to_owned(), process.arrival_time);
-
-        if let Some(first_job_name) = &self.first_job {
-            if self.job_list[first_job_name] <= process.arrival_time {
-                return;
-            }
-        }
-
-        self.first_job = Some(process.name.to_owned());
-        process.select(time);
-
+        // the arrival time for a new process, or the end of its last burst when it comes back
+        self.job_list.insert(process.name.to_owned(), (time, process.arrival_time));
     }
 
     fn pre_tick(&mut self, process: &mut super::process::Process, time: i32) {
+        // the cpu is free, so pick once every process arriving this tick is in
+        if self.first_job.is_none() {
+            self.find_first_job();
+        }
 
         if let Some(name) = &self.first_job {
 
@@ -76,16 +75,33 @@ impl Scheduler for FCFS {
     fn on_finish(&mut self, finished_process: &Process, _: i32) {
         
         self.job_list.remove(&finished_process.name);
-        self.find_first_job();
+        self.first_job = None;
 
     }
 
     fn selected_process_name(&self) -> Option<String> {
-        // println!("name is {:?}", self.first_job);
-        match &self.first_job {
-            Some(job_name) => Some(job_name.to_string()),
-            None => None
-        }
+        self.first_job.clone()
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        let mut waiting: Vec<(&String, &(i32, i32))> = self.job_list.iter()
+            .filter(|(name, _)| self.first_job.as_ref() != Some(*name))
+            .collect();
+        waiting.sort_by_key(|(name, joined)| (**joined, *name));
+
+        waiting.into_iter().map(|(name, _)| name.to_owned()).collect()
+    }
+
+    fn keyword(&self) -> String {
+        "fcfs".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
     }
 
     
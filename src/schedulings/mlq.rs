@@ -0,0 +1,285 @@
+// a multi-level queue, each class of process gets one of the other schedulers to itself
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::process::{Process, ProcessState};
+use super::registry::{build_scheduler, restore_as, Registration};
+use super::scheduler::Scheduler;
+
+/// How the classes share the cpu
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Policy {
+    /// A class only runs while every class above it has nothing to run
+    Strict,
+    /// The classes take turns, each holding the cpu for up to its slice
+    Slice,
+}
+
+/// A class of processes and the scheduler that picks between them
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Class {
+    pub name: String,
+    /// How many ticks in a row the class may hold the cpu under `Policy::Slice`
+    pub slice: Option<i32>,
+    #[serde(with = "saved_scheduler")]
+    pub scheduler: Box<dyn Scheduler>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Mlq {
+    policy: Policy,
+    /// Highest priority first
+    classes: Vec<Class>,
+    /// The class of every process that has arrived
+    process_class: HashMap<String, usize>,
+    /// The class holding the cpu
+    active: Option<usize>,
+    active_since: i32,
+    /// The last time a class was picked, so it only happens once a tick
+    picked_at: Option<i32>,
+}
+
+impl Mlq {
+    /// How `use mlq` builds the scheduler
+    pub fn registration() -> Registration {
+        Registration {
+            keyword: "mlq",
+            description: "Multi-Level Queue, each `class` of process has its own scheduler, with strict priority or time slices between them",
+            params: &["policy", "classes"],
+            build: |params| Ok(Box::new(Mlq::from_params(params)?)),
+            restore: restore_as::<Mlq>,
+        }
+    }
+
+    pub fn new(policy: Policy, classes: Vec<Class>) -> Mlq {
+        Mlq {
+            policy,
+            classes,
+            process_class: HashMap::new(),
+            active: None,
+            active_since: 0,
+            picked_at: None,
+        }
+    }
+
+    /// Reads the `policy` and `classes` parameters, each class being a table with a `name`, the `use` keyword of its scheduler,
+    /// an optional `slice` and any parameters that scheduler takes
+    pub fn from_params(params: &HashMap<String, Value>) -> Result<Mlq, Box<dyn Error>> {
+        let policy = match params.get("policy").map(Value::as_str) {
+            None | Some(Some("strict")) => Policy::Strict,
+            Some(Some("slice")) => Policy::Slice,
+            Some(_) => return Err("`policy` must be strict or slice".into()),
+        };
+
+        let Some(tables) = params.get("classes").and_then(Value::as_array).filter(|tables| !tables.is_empty()) else {
+            return Err("mlq needs at least one `class`".into());
+        };
+
+        let mut classes: Vec<Class> = vec![];
+        for table in tables {
+            let class = read_class(table, policy)?;
+            if classes.iter().any(|c| c.name == class.name) {
+                return Err(format!("class {} is given more than once", class.name).into());
+            }
+            classes.push(class);
+        }
+
+        Ok(Mlq::new(policy, classes))
+    }
+
+    /// Whether the class has a process to run, picked or still waiting
+    fn has_work(&self, index: usize) -> bool {
+        let scheduler = &self.classes[index].scheduler;
+        scheduler.selected_process_name().is_some() || !scheduler.ready_queue().is_empty()
+    }
+
+    fn pick_class(&mut self, time: i32) {
+        let count = self.classes.len();
+
+        if let (Policy::Slice, Some(active)) = (self.policy, self.active) {
+            let slice = self.classes[active].slice.unwrap_or(1);
+            if self.has_work(active) && time - self.active_since < slice {
+                return;
+            }
+
+            // the turn passes to the next class down with work, wrapping back around to this one
+            self.active = (1..=count).map(|step| (active + step) % count).find(|index| self.has_work(*index));
+        }
+        else {
+            let highest = (0..count).find(|index| self.has_work(*index));
+            if highest == self.active {
+                return;
+            }
+            self.active = highest;
+        }
+
+        self.active_since = time;
+    }
+}
+
+impl Scheduler for Mlq {
+    fn descriptive_name(&self) -> String {
+        let classes: Vec<String> = self.classes.iter()
+            .map(|class| match (self.policy, class.slice) {
+                (Policy::Slice, Some(slice)) => format!("{} ({}, {} ticks)", class.name, class.scheduler.keyword(), slice),
+                _ => format!("{} ({})", class.name, class.scheduler.keyword()),
+            })
+            .collect();
+
+        match self.policy {
+            Policy::Strict => format!("Multi-Level Queue, strict priority between {}", classes.join(", ")),
+            Policy::Slice => format!("Multi-Level Queue, time slices between {}", classes.join(", ")),
+        }
+    }
+
+    fn on_arrive(&mut self, process: &mut Process, time: i32) {
+        let Some(class) = self.classes.iter().position(|c| Some(&c.name) == process.class.as_ref()) else {
+            return;
+        };
+        self.process_class.insert(process.name.to_owned(), class);
+        self.classes[class].scheduler.on_arrive(process, time);
+    }
+
+    fn pre_tick(&mut self, process: &mut Process, time: i32) {
+        if self.picked_at != Some(time) {
+            self.picked_at = Some(time);
+            self.pick_class(time);
+        }
+
+        let Some(&class) = self.process_class.get(&process.name) else {
+            return;
+        };
+
+        // a class that lost the cpu keeps its pick for when it gets the cpu back
+        if self.active != Some(class) {
+            process.deselect();
+            return;
+        }
+
+        let scheduler = &mut self.classes[class].scheduler;
+        scheduler.pre_tick(process, time);
+
+        if scheduler.selected_process_name().as_ref() == Some(&process.name) && process.state != Some(ProcessState::Running) {
+            process.select(time);
+        }
+    }
+
+    fn on_tick(&mut self, process: &mut Process, time: i32) {
+        // a class that did not hold the cpu this tick is frozen, so a quantum is not used up while waiting
+        if let Some(&class) = self.process_class.get(&process.name) {
+            if self.active == Some(class) {
+                self.classes[class].scheduler.on_tick(process, time);
+            }
+        }
+    }
+
+    fn on_finish(&mut self, process: &Process, time: i32) {
+        if let Some(&class) = self.process_class.get(&process.name) {
+            self.classes[class].scheduler.on_finish(process, time);
+        }
+    }
+
+    fn selected_process_name(&self) -> Option<String> {
+        self.active.and_then(|class| self.classes[class].scheduler.selected_process_name())
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        self.classes.iter().enumerate()
+            .flat_map(|(index, class)| {
+                let suspended = match self.active == Some(index) {
+                    true => None,
+                    false => class.scheduler.selected_process_name(),
+                };
+                suspended.into_iter().chain(class.scheduler.ready_queue())
+            })
+            .collect()
+    }
+
+    fn keyword(&self) -> String {
+        "mlq".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<Value> {
+        serde_json::to_value(self)
+    }
+
+    fn summary(&self, processes: &[Process]) -> Vec<String> {
+        self.classes.iter()
+            .flat_map(|class| {
+                let members: Vec<Process> = processes.iter()
+                    .filter(|p| p.class.as_ref() == Some(&class.name))
+                    .cloned()
+                    .collect();
+                class.scheduler.summary(&members)
+            })
+            .collect()
+    }
+
+    fn check_process(&self, process: &Process) -> Result<(), String> {
+        match &process.class {
+            None => Err(format!("process {} needs a `class` under mlq", process.name)),
+            Some(class) if !self.classes.iter().any(|c| &c.name == class) => {
+                Err(format!("process {} has class {}, which mlq does not have", process.name, class))
+            },
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+fn read_class(table: &Value, policy: Policy) -> Result<Class, Box<dyn Error>> {
+    let fields = table.as_object().ok_or("each mlq class needs a `name` and a scheduler to `use`")?;
+    let name = fields.get("name").and_then(Value::as_str).ok_or("each mlq class needs a `name`")?;
+    let keyword = fields.get("use").and_then(Value::as_str)
+        .ok_or_else(|| format!("class {} needs a scheduler to `use`", name))?;
+
+    if keyword == "mlq" {
+        return Err(format!("class {} cannot use mlq itself", name).into());
+    }
+
+    let slice = match (fields.get("slice"), policy) {
+        (None, Policy::Strict) => None,
+        (None, Policy::Slice) => return Err(format!("class {} needs a `slice` under the slice policy", name).into()),
+        (Some(_), Policy::Strict) => return Err(format!("class {} has a `slice`, which only the slice policy uses", name).into()),
+        (Some(slice), Policy::Slice) => match slice.as_i64() {
+            Some(slice @ 1..=0x7fff_ffff) => Some(slice as i32),
+            _ => return Err(format!("class {} needs a `slice` of at least 1", name).into()),
+        },
+    };
+
+    // everything else is for the class's own scheduler
+    let params: HashMap<String, Value> = fields.iter()
+        .filter(|(key, _)| !matches!(key.as_str(), "name" | "use" | "slice"))
+        .map(|(key, value)| (key.to_owned(), value.clone()))
+        .collect();
+    let scheduler = build_scheduler(keyword, &params).map_err(|e| format!("class {}: {}", name, e))?;
+
+    Ok(Class { name: name.to_string(), slice, scheduler })
+}
+
+/// Saves a class's scheduler as its keyword and state, the same way a checkpoint does
+mod saved_scheduler {
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    use super::super::registry::restore_scheduler;
+    use super::super::scheduler::Scheduler;
+
+    #[allow(clippy::borrowed_box)]
+    pub fn serialize<S: Serializer>(scheduler: &Box<dyn Scheduler>, serializer: S) -> Result<S::Ok, S::Error> {
+        let state = scheduler.save_state().map_err(ser::Error::custom)?;
+        (scheduler.keyword(), state).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<dyn Scheduler>, D::Error> {
+        let (keyword, state) = <(String, Value)>::deserialize(deserializer)?;
+        restore_scheduler(&keyword, state).map_err(|e| de::Error::custom(e.to_string()))
+    }
+}
@@ -3,7 +3,26 @@ pub mod fcfs;
 pub mod sjf;
 pub mod rr;
 pub mod real_sjf;
+pub mod sjf_predict;
+pub mod mlq;
+pub mod custom;
+pub mod scenario;
+pub mod report;
 pub mod schedule;
+pub mod parallel;
 pub mod process;
+pub mod registry;
+pub mod reference;
+pub mod batch;
+pub mod bounds;
+pub mod compare;
 pub mod cpu;
-pub mod scheduler;
\ No newline at end of file
+pub mod debugger;
+pub mod fairness;
+pub mod optimal;
+pub mod scheduler;
+pub mod sink;
+pub mod snapshot;
+pub mod timeline;
+pub mod workload;
+pub mod tui;
\ No newline at end of file
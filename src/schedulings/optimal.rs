@@ -0,0 +1,176 @@
+// the best average wait any schedule could reach for a small workload, checked against what the sjf schedulers actually got
+
+use std::error::Error;
+use std::io::{Result, Write};
+
+use super::batch::RunSummary;
+use super::compare::with_scheduler;
+use super::cpu::CPU;
+use super::process::Process;
+use super::schedule::ScheduleModel;
+use super::sink::DiscardSink;
+
+/// Every ordering of this many processes is still quick to try
+pub const MAX_PROCESSES: usize = 10;
+
+/// The lowest average wait found, and the order the processes finished in to get it
+pub struct Optimum {
+    pub average_wait: f64,
+    pub order: Vec<String>,
+}
+
+/// How close one scheduler came to the best schedule of its kind
+pub struct Check {
+    /// `non-preemptive` or `preemptive`
+    pub kind: &'static str,
+    pub best: Optimum,
+    pub scheduler: String,
+    /// None when the scheduler left processes unfinished within `runfor`
+    pub average_wait: Option<f64>,
+}
+
+impl Check {
+    /// How much longer the scheduler made processes wait on average than they had to
+    pub fn gap(&self) -> Option<f64> {
+        self.average_wait.map(|wait| wait - self.best.average_wait)
+    }
+}
+
+/// Runs the model under realSJF and sjf and finds the best non-preemptive and preemptive schedules to hold them against
+pub fn check(model: &ScheduleModel) -> std::result::Result<Vec<Check>, Box<dyn Error>> {
+    let processes = &model.process_list;
+    check_workload(processes)?;
+
+    let checks = [
+        ("non-preemptive", "realSJF", best_non_preemptive(processes)),
+        ("preemptive", "sjf", best_preemptive(processes)),
+    ];
+
+    checks.into_iter()
+        .map(|(kind, scheduler, best)| {
+            let mut variant = with_scheduler(model, scheduler, &Default::default())?;
+            CPU::with_sink(Box::new(DiscardSink::default())).run(&mut variant);
+
+            let summary = RunSummary::from_model(&variant);
+            let average_wait = (summary.finished == summary.processes).then_some(summary.average_wait);
+
+            Ok(Check { kind, best, scheduler: scheduler.to_string(), average_wait })
+        })
+        .collect()
+}
+
+/// Prints a line per check with the best order found
+pub fn write_checks(out: &mut dyn Write, checks: &[Check]) -> Result<()> {
+    for check in checks {
+        let best = format!("{}: best average wait {:.2} (finishing {})", check.kind, check.best.average_wait, check.best.order.join(" "));
+
+        match (check.average_wait, check.gap()) {
+            (Some(wait), Some(gap)) => writeln!(out, "{}, {} {:.2}, {:+.2} over", best, check.scheduler, wait, gap)?,
+            _ => writeln!(out, "{}, {} did not finish", best, check.scheduler)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Only plain workloads can be checked, one burst per process and nothing waiting on anything else
+fn check_workload(processes: &[Process]) -> std::result::Result<(), Box<dyn Error>> {
+    if processes.is_empty() {
+        return Err("there are no processes to check".into());
+    }
+    if processes.len() > MAX_PROCESSES {
+        return Err(format!("only up to {} processes can be checked, found {}", MAX_PROCESSES, processes.len()).into());
+    }
+    if let Some(process) = processes.iter().find(|p| p.bursts.len() > 1) {
+        return Err(format!("process {} has more than one burst, which the check does not handle", process.name).into());
+    }
+    if let Some(process) = processes.iter().find(|p| !p.after.is_empty()) {
+        return Err(format!("process {} waits on other processes, which the check does not handle", process.name).into());
+    }
+
+    Ok(())
+}
+
+/// Tries every order of running the processes to completion, each starting as soon as it has arrived and the one before it is done.
+/// Leaving the cpu idle any longer never helps, so this covers every non-preemptive schedule worth having
+pub fn best_non_preemptive(processes: &[Process]) -> Optimum {
+    let mut remaining: Vec<&Process> = processes.iter().collect();
+    let mut order = vec![];
+    let mut best = (i64::MAX, vec![]);
+
+    try_orders(&mut remaining, &mut order, 0, 0, &mut best);
+
+    Optimum {
+        average_wait: best.0 as f64 / processes.len().max(1) as f64,
+        order: best.1,
+    }
+}
+
+/// Extends the order with each remaining process in turn, dropping any order that already waits as long as the best one found
+fn try_orders<'a>(remaining: &mut Vec<&'a Process>, order: &mut Vec<&'a Process>, free_at: i64, wait: i64, best: &mut (i64, Vec<String>)) {
+    if wait >= best.0 {
+        return;
+    }
+    if remaining.is_empty() {
+        *best = (wait, order.iter().map(|p| p.name.to_owned()).collect());
+        return;
+    }
+
+    for index in 0..remaining.len() {
+        let process = remaining.remove(index);
+        let start = free_at.max(process.arrival_time as i64);
+
+        order.push(process);
+        try_orders(remaining, order, start + process.burst_time as i64, wait + start - process.arrival_time as i64, best);
+        order.pop();
+
+        remaining.insert(index, process);
+    }
+}
+
+/// Always running the process with the least time left gives the lowest total finish time, and so the lowest total wait,
+/// of any preemptive schedule on one cpu, so the best schedule is simulated directly instead of searched for
+pub fn best_preemptive(processes: &[Process]) -> Optimum {
+    let mut remaining: Vec<i64> = processes.iter().map(|p| p.burst_time as i64).collect();
+    let mut finished: Vec<(i64, usize)> = vec![];
+    let mut time = processes.iter().map(|p| p.arrival_time as i64).min().unwrap_or_default();
+
+    while finished.len() < processes.len() {
+        let shortest = (0..processes.len())
+            .filter(|i| remaining[*i] > 0 && processes[*i].arrival_time as i64 <= time)
+            .min_by_key(|i| remaining[*i]);
+
+        let Some(index) = shortest else {
+            // idle until the next arrival
+            time = processes.iter().enumerate()
+                .filter(|(i, _)| remaining[*i] > 0)
+                .map(|(_, p)| p.arrival_time as i64)
+                .min()
+                .unwrap_or(time);
+            continue;
+        };
+
+        // run until it finishes or the next arrival might be shorter
+        let next_arrival = processes.iter()
+            .map(|p| p.arrival_time as i64)
+            .filter(|arrival| *arrival > time)
+            .min()
+            .unwrap_or(i64::MAX);
+        let ran = remaining[index].min(next_arrival - time);
+
+        remaining[index] -= ran;
+        time += ran;
+        if remaining[index] == 0 {
+            finished.push((time, index));
+        }
+    }
+
+    let wait: i64 = finished.iter()
+        .map(|(finish, index)| finish - processes[*index].arrival_time as i64 - processes[*index].burst_time as i64)
+        .sum();
+
+    Optimum {
+        average_wait: wait as f64 / processes.len().max(1) as f64,
+        order: finished.iter().map(|(_, index)| processes[*index].name.to_owned()).collect(),
+    }
+}
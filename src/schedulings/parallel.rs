@@ -0,0 +1,39 @@
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::thread;
+
+/// One job per core, or a single job if the core count is unknown
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map(NonZeroUsize::get).unwrap_or(1)
+}
+
+/// Maps every item on up to `jobs` threads, handing the results back in the same order as the items
+pub fn map_parallel<T: Send, R: Send>(jobs: usize, items: Vec<T>, f: impl Fn(T) -> R + Sync) -> Vec<R> {
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let count = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..count).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                // the queue lock is dropped before the item runs so the other threads can take the next one
+                let next = queue.lock().expect("a job panicked").next();
+                let Some((index, item)) = next else {
+                    break;
+                };
+
+                let result = f(item);
+                results.lock().expect("a job panicked")[index] = Some(result);
+            });
+        }
+    });
+
+    results.into_inner().expect("a job panicked").into_iter()
+        .map(|result| result.expect("every item is mapped once"))
+        .collect()
+}
@@ -1,25 +1,70 @@
-use std::{num::ParseIntError, str::SplitWhitespace};
+use std::error::Error;
 
-#[derive(PartialEq, Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+use super::schedule::set_once;
+
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum ProcessState {
     Ready,
     Running,
+    /// Doing I/O between two cpu bursts, away from the cpu and the ready queue
+    Io,
 }
 
 /// A data structure representing a running Program
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Process {
     pub name: String,
     pub arrival_time: i32,
+    /// The total of every cpu burst
     pub burst_time: i32,
     pub time_remaining: i32,
+    /// The separate cpu bursts, a process goes back to the ready queue between them
+    #[serde(default)]
+    pub bursts: Vec<i32>,
+    /// How many of the bursts are done
+    #[serde(default)]
+    pub burst_index: usize,
+    /// How many ticks of I/O the process does after each burst but the last, before it is ready again
+    #[serde(default)]
+    pub io: i32,
+    /// Which class of an `mlq` scheduler the process belongs to
+    #[serde(default)]
+    pub class: Option<String>,
+    /// The processes that have to finish before this one is released to the scheduler
+    #[serde(default)]
+    pub after: Vec<String>,
+    /// When the process was released to the scheduler, only set for processes that wait on others
+    #[serde(default)]
+    pub release_time: Option<i32>,
     pub state: Option<ProcessState>,
 
     pub turnaround_time: i32,
     pub response_time: i32,
     pub wait_time: i32,
     pub finish_time: i32,
-    pub last_selection_time: Option<i32>
+    pub last_selection_time: Option<i32>,
+
+    /// How many times the process was taken off the cpu before finishing
+    #[serde(default)]
+    pub preemptions: i32,
+    /// How many times the process was picked to run
+    #[serde(default)]
+    pub selections: i32,
+    /// How many separate stretches of ticks the process ran for, a reselection straight after a preemption continues the same slice
+    #[serde(default)]
+    pub slices: i32,
+    /// How many ticks in a row the process has now been waiting in the ready queue
+    #[serde(default)]
+    pub ready_streak: i32,
+    /// The longest the process ever waited in the ready queue without running
+    #[serde(default)]
+    pub max_ready_streak: i32,
+    #[serde(default)]
+    last_run_time: Option<i32>,
+    #[serde(default)]
+    burst_ended_at: Option<i32>,
 }
 
 impl Process {
@@ -29,30 +74,82 @@ impl Process {
             arrival_time,
             burst_time,
             time_remaining: burst_time,
+            bursts: vec![burst_time],
+            burst_index: 0,
+            io: 0,
+            class: None,
+            after: vec![],
+            release_time: None,
             state: None,
             turnaround_time: 0,
             response_time: 0,
             wait_time: 0,
             finish_time: 0,
-            last_selection_time: None
+            last_selection_time: None,
+            preemptions: 0,
+            selections: 0,
+            slices: 0,
+            ready_streak: 0,
+            max_ready_streak: 0,
+            last_run_time: None,
+            burst_ended_at: None,
         }
     }
 
-    /// Takes a split whitespace line and attempts to great a new process
-    pub fn parse(mut process_line: SplitWhitespace) -> Result<Process, ParseIntError>{
-        // process name
-        process_line.next();
-        let process_name = process_line.nth(0).unwrap_or_default().to_string();
+    /// A process that needs the cpu for each of the given bursts in turn
+    pub fn with_bursts(name: String, arrival_time: i32, bursts: Vec<i32>) -> Process {
+        let mut process = Process::new(name, arrival_time, bursts.iter().sum());
+        process.bursts = bursts;
+        process
+    }
+
+    /// Reads the `key value` pairs that follow `process` on an input line, in any order
+    pub fn parse(fields: &[&str]) -> Result<Process, Box<dyn Error>> {
+        let mut name: Option<String> = None;
+        let mut arrival_time: Option<i32> = None;
+        let mut burst_time: Option<i32> = None;
+        let mut bursts: Option<Vec<i32>> = None;
+        let mut io: Option<i32> = None;
+        let mut class: Option<String> = None;
+        let mut after: Vec<String> = vec![];
 
-        // arrival time
-        process_line.next();
-        let arrival_time = process_line.nth(0).unwrap_or_default().parse::<i32>()?;
+        let mut rest = fields;
+        while let [key, tail @ ..] = rest {
+            // `after` names every process on the rest of the line, as in `after A B`
+            if *key == "after" {
+                after = tail.iter().flat_map(|names| names.split(',')).filter(|name| !name.is_empty()).map(str::to_string).collect();
+                if after.is_empty() {
+                    return Err("`after` needs at least one process name".into());
+                }
+                break;
+            }
 
-        // burst time
-        process_line.next();
-        let burst_time = process_line.nth(0).unwrap_or_default().parse::<i32>()?;
+            let [value, tail @ ..] = tail else {
+                return Err(format!("`{}` is missing a value", key).into());
+            };
+            rest = tail;
 
-        Ok(Process::new(process_name, arrival_time, burst_time))
+            match *key {
+                "name" => set_once(&mut name, key, value.to_string())?,
+                "arrival" => set_once(&mut arrival_time, key, parse_number(key, value)?)?,
+                "burst" => set_once(&mut burst_time, key, parse_number(key, value)?)?,
+                "bursts" => {
+                    let lengths = value.split(',').map(|length| parse_number(key, length)).collect::<Result<_, _>>()?;
+                    set_once(&mut bursts, key, lengths)?
+                },
+                "io" => set_once(&mut io, key, parse_number(key, value)?)?,
+                "class" => set_once(&mut class, key, value.to_string())?,
+                _ => return Err(format!("unknown process field `{}`", key).into()),
+            }
+        }
+
+        let name = name.ok_or("process is missing a `name`")?;
+        let arrival_time = arrival_time.ok_or("process is missing an `arrival`")?;
+        let mut process = Process::with_bursts(name, arrival_time, pick_bursts(burst_time, bursts)?);
+        process.io = io.unwrap_or_default();
+        process.class = class;
+        process.after = after;
+        Ok(process)
     }
 
     /// Simulates the ticking of time by 1 given the time that the process is ticking to
@@ -62,7 +159,9 @@ impl Process {
             match state {
                 ProcessState::Ready => {
                     self.wait_time += 1;
-                    
+                    self.ready_streak += 1;
+                    self.max_ready_streak = self.max_ready_streak.max(self.ready_streak);
+
                     // if we have not been selected before
                     if self.burst_time == self.time_remaining {
                         self.response_time += 1;
@@ -70,11 +169,29 @@ impl Process {
                 },
                 ProcessState::Running => {
                     self.time_remaining -= 1;
+                    self.ready_streak = 0;
+
+                    // running the tick before this one means we are still in the same slice
+                    if self.last_run_time != Some(cur_time - 1) {
+                        self.slices += 1;
+                    }
+                    self.last_run_time = Some(cur_time);
 
                     if self.time_remaining == 0 {
                         self.finish(cur_time);
                     }
+                    else if self.burst_remaining() == 0 {
+                        // not a preemption, the process gave up the cpu itself
+                        self.burst_index += 1;
+                        self.burst_ended_at = Some(cur_time);
+                        self.state = match self.io {
+                            0 => Some(ProcessState::Ready),
+                            _ => Some(ProcessState::Io),
+                        };
+                    }
                 },
+                // neither running nor waiting for the cpu
+                ProcessState::Io => self.ready_streak = 0,
             }
         }
     }
@@ -82,13 +199,18 @@ impl Process {
     /// Sets the process state to running (if not already running)
     /// Also prints selection time and remaining time for the process
     pub fn select(&mut self, cur_time: i32) {
+        self.selections += 1;
         self.state = Some(ProcessState::Running);
         self.last_selection_time = Some(cur_time);
     }
 
     /// Sets the process state back to ready (if not already finished)
+    /// Counts a preemption if the process was running
     pub fn deselect(&mut self) {
         if !self.finished() {
+            if self.state == Some(ProcessState::Running) {
+                self.preemptions += 1;
+            }
             self.state = Some(ProcessState::Ready);
         }
     }
@@ -99,13 +221,94 @@ impl Process {
         self.finish_time = cur_time;
     }
 
+    /// Hands a held back process to the scheduler, counting the ticks it was held as waiting since they are part of its turnaround
+    pub fn release(&mut self, cur_time: i32) {
+        let held = cur_time - self.arrival_time;
+        self.wait_time += held;
+        self.turnaround_time += held;
+        self.response_time += held;
+
+        self.release_time = Some(cur_time);
+        self.deselect();
+    }
+
     /// Returns whether the process has finished
     pub fn finished(&self) -> bool {
         self.time_remaining == 0
     }
 
+    /// How much of the current cpu burst is left
+    pub fn burst_remaining(&self) -> i32 {
+        self.time_remaining - self.bursts.iter().skip(self.burst_index + 1).sum::<i32>()
+    }
+
+    /// Returns whether the process finished one of its bursts, with more to follow, at the given time
+    pub fn ended_burst(&self, time: i32) -> bool {
+        self.burst_ended_at == Some(time)
+    }
+
+    /// Returns whether the process finished the I/O after one of its bursts at the given time, and is ready again
+    pub fn ended_io(&self, time: i32) -> bool {
+        self.in_io() && self.burst_ended_at.is_some_and(|ended| ended + self.io == time)
+    }
+
+    /// Returns whether the process is doing I/O between bursts, which the scheduler does not see
+    pub fn in_io(&self) -> bool {
+        self.state == Some(ProcessState::Io)
+    }
+
+    /// The ticks of I/O the process does in all if it runs to the end
+    pub fn io_time(&self) -> i32 {
+        self.io.saturating_mul((self.bursts.len() as i32 - 1).max(0))
+    }
+
     /// Returns whether the process has arrived at the given time
     pub fn arrived(&self, time: i32) -> bool {
         self.arrival_time <= time
     }
-}
\ No newline at end of file
+
+    /// Returns whether the scheduler has the process by the given time, which for a process waiting on others is only once they finish
+    pub fn released_by(&self, time: i32) -> bool {
+        match self.after.is_empty() {
+            true => self.arrived(time),
+            false => self.release_time.is_some_and(|release_time| release_time <= time),
+        }
+    }
+
+    /// A short label for the state of the process at the given time, used when showing a run live
+    pub fn state_name(&self, time: i32) -> &'static str {
+        if self.finished() {
+            return "finished";
+        }
+
+        match self.state {
+            Some(ProcessState::Running) => "running",
+            Some(ProcessState::Ready) => "ready",
+            Some(ProcessState::Io) => "in I/O",
+            None if !self.arrived(time) => "not arrived",
+            None if !self.released_by(time) => "blocked",
+            None => "waiting",
+        }
+    }
+}
+
+/// The bursts of a process given either a single `burst` or a list of `bursts`
+pub fn pick_bursts(burst: Option<i32>, bursts: Option<Vec<i32>>) -> Result<Vec<i32>, Box<dyn Error>> {
+    let bursts = match (burst, bursts) {
+        (Some(burst), None) => vec![burst],
+        (None, Some(bursts)) => bursts,
+        (Some(_), Some(_)) => return Err("process gives both a `burst` and `bursts`".into()),
+        (None, None) => return Err("process is missing a `burst`".into()),
+    };
+
+    // the total is kept as the process's `burst_time`
+    if bursts.iter().try_fold(0i32, |total, burst| total.checked_add(*burst)).is_none() {
+        return Err("process `bursts` add up to more than a whole number can hold".into());
+    }
+
+    Ok(bursts)
+}
+
+fn parse_number(key: &str, value: &str) -> Result<i32, Box<dyn Error>> {
+    value.parse::<i32>().map_err(|_| format!("`{}` must be a whole number, found `{}`", key, value).into())
+}
@@ -1,65 +1,63 @@
 
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
+use super::registry::{aging_param, restore_as, Registration};
 use super::scheduler::Scheduler;
 use super::process::{Process, ProcessState};
 
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 
 pub struct RealSjf {
-    /// A hashmap that contains keys that represent the process name and a integer for burst time
+    /// A hashmap that contains keys that represent the process name and a integer for the length of its current burst
     pub job_list: HashMap<String, i32>,
-    pub shortest_job: Option<String>
+    pub shortest_job: Option<String>,
+    /// Lowers the key of a waiting process by one for every this many ticks it has waited since it last ran
+    #[serde(default)]
+    pub aging: Option<i32>,
 }
 
 
 impl RealSjf {
-    fn find_shortest_job(&mut self) {
-        let mut shortest = ("", &i32::MAX);
-
-        for (name, burst_time) in self.job_list.iter() {
-            
-            if shortest.1 > burst_time {
-                
-                shortest = (name, burst_time)
-            }
+    /// How `use realSJF` builds the scheduler
+    pub fn registration() -> Registration {
+        Registration {
+            keyword: "realSJF",
+            description: "non-preemptive Shortest Job First, picks the shortest burst whenever the cpu frees up",
+            params: &["aging"],
+            build: |params| Ok(Box::new(RealSjf { aging: aging_param(params)?, ..RealSjf::default() })),
+            restore: restore_as::<RealSjf>,
         }
+    }
 
-        if shortest.0 == "" {
-            self.shortest_job = None;
-        }
-        else {
-            self.shortest_job = Some(shortest.0.to_string())
-        }
+    /// Picks the shortest job, ties going to the name that sorts first
+    fn find_shortest_job(&mut self) {
+        self.shortest_job = self.job_list.iter()
+            .min_by_key(|(name, burst_time)| (**burst_time, *name))
+            .map(|(name, _)| name.to_owned());
     }
 }
 
 impl Scheduler for RealSjf {
 
     fn descriptive_name(&self) -> String {
-        "real Shortest Job First (non-preemptive)".to_string()
-    }
-
-    fn on_arrive(&mut self, process: &mut super::process::Process, time: i32) {
-        
-        self.job_list.insert(process.name.to_owned(), process.burst_time);
-
-        // if let Some(first_job_name) = &self.first_job {
-        //     if self.job_list[first_job_name] <= process.arrival_time {
-        //         return;
-        //     }
-        // }
-
-        if self.job_list.len() == 1
-        {
-            self.shortest_job = Some(process.name.to_owned());
-            process.select(time);
+        match self.aging {
+            Some(aging) => format!("real Shortest Job First (non-preemptive) with aging every {} ticks", aging),
+            None => "real Shortest Job First (non-preemptive)".to_string(),
         }
+    }
 
+    fn on_arrive(&mut self, process: &mut super::process::Process, _time: i32) {
+        self.job_list.insert(process.name.to_owned(), process.burst_remaining());
     }
 
     fn pre_tick(&mut self, process: &mut super::process::Process, time: i32) {
+        // the cpu is free, so pick once every process arriving this tick is in, not just the first
+        if self.shortest_job.is_none() {
+            self.find_shortest_job();
+        }
 
         if let Some(name) = &self.shortest_job {
 
@@ -76,8 +74,13 @@ impl Scheduler for RealSjf {
         }
     }
 
-    fn on_tick(&mut self, _process: &mut super::process::Process, _time: i32) {
-        // do nothing
+    fn on_tick(&mut self, process: &mut super::process::Process, _time: i32) {
+        // the selected process keeps the cpu whatever its key, so only the waiting ones age
+        if let Some(aging) = self.aging {
+            if self.shortest_job.as_ref() != Some(&process.name) {
+                self.job_list.insert(process.name.to_owned(), process.burst_remaining() - process.ready_streak / aging);
+            }
+        }
     }
 
     
@@ -86,16 +89,34 @@ impl Scheduler for RealSjf {
         let removed_value = self.job_list.remove(&finished_process.name);
 
         if let Some(_value) = removed_value {
-            self.find_shortest_job();
+            self.shortest_job = None;
         }
 
     }
 
     fn selected_process_name(&self) -> Option<String> {
-        match &self.shortest_job {
-            Some(job_name) => Some(job_name.to_string()),
-            None => None
-        }
+        self.shortest_job.clone()
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        let mut waiting: Vec<(&String, &i32)> = self.job_list.iter()
+            .filter(|(name, _)| self.shortest_job.as_ref() != Some(*name))
+            .collect();
+        waiting.sort_by_key(|(name, burst_time)| (**burst_time, *name));
+
+        waiting.into_iter().map(|(name, _)| name.to_owned()).collect()
+    }
+
+    fn keyword(&self) -> String {
+        "realSJF".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
     }
 
     
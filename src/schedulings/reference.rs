@@ -0,0 +1,250 @@
+// deliberately naive simulators of the plain schedulers, one loop over a vector each, that the engine's log is held against
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+use serde_json::Value;
+
+use super::cpu::CPU;
+use super::process::Process;
+use super::registry::build_scheduler;
+use super::sink::Sink;
+use super::workload::model;
+
+/// The schedulers with a reference simulator
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Reference {
+    Fcfs,
+    Sjf,
+    RealSjf,
+    Rr(i32),
+}
+
+impl Reference {
+    /// Every reference, rr with a quantum that expires every tick and one that often outlasts a burst
+    pub const ALL: [Reference; 5] = [Reference::Fcfs, Reference::Sjf, Reference::RealSjf, Reference::Rr(1), Reference::Rr(3)];
+
+    /// The `use` keyword of the engine's scheduler
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Reference::Fcfs => "fcfs",
+            Reference::Sjf => "sjf",
+            Reference::RealSjf => "realSJF",
+            Reference::Rr(_) => "rr",
+        }
+    }
+
+    fn params(&self) -> HashMap<String, Value> {
+        match self {
+            Reference::Rr(quantum) => HashMap::from([("quantum".to_string(), Value::from(*quantum))]),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reference::Rr(quantum) => write!(f, "rr quantum {}", quantum),
+            _ => write!(f, "{}", self.keyword()),
+        }
+    }
+}
+
+/// Where the reference and the engine first wrote something different
+pub struct Divergence {
+    /// Counted from the first line after the header
+    pub line: usize,
+    /// The tick of the line, when it is one of the `Time` lines
+    pub tick: Option<i32>,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl Divergence {
+    fn at(line: usize, expected: Option<String>, actual: Option<String>) -> Divergence {
+        let tick = expected.as_deref().or(actual.as_deref())
+            .and_then(|line| line.strip_prefix("Time "))
+            .and_then(|rest| rest.split(':').next())
+            .and_then(|tick| tick.trim().parse().ok());
+
+        Divergence { line, tick, expected, actual }
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line = |line: &Option<String>| line.clone().unwrap_or_else(|| "nothing".to_string());
+
+        match self.tick {
+            Some(tick) => write!(f, "first difference at time {} (line {})", tick, self.line + 1)?,
+            None => write!(f, "first difference on line {}", self.line + 1)?,
+        }
+        write!(f, ": expected `{}`, the engine wrote `{}`", line(&self.expected), line(&self.actual))
+    }
+}
+
+/// A process as the reference sees it
+struct Job<'a> {
+    process: &'a Process,
+    remaining: i32,
+    /// When it last joined the ready queue
+    joined: i32,
+    first_run: Option<i32>,
+    finish: Option<i32>,
+}
+
+/// The log the engine should write for the processes after its two header lines, or why the reference cannot say.
+/// Every tick the running process gets the tick that just passed, the arrivals join the queue, an expired quantum sends
+/// the process to the back of it, a finished process leaves, and the cpu is handed to whichever process should have it
+pub fn simulate(reference: Reference, processes: &[Process], time_units: i32) -> Result<Vec<String>, String> {
+    if let Some(process) = processes.iter().find(|p| p.bursts.len() > 1 || !p.after.is_empty()) {
+        return Err(format!("process {} has more than one burst or waits on others, which the reference does not handle", process.name));
+    }
+
+    let mut jobs: Vec<Job> = processes.iter()
+        .map(|process| Job { process, remaining: process.burst_time, joined: 0, first_run: None, finish: None })
+        .collect();
+    let mut log = vec![];
+    // waiting jobs in the order they joined, the running one is not in here
+    let mut queue: Vec<usize> = vec![];
+    let mut running: Option<usize> = None;
+    let mut ran = 0;
+
+    for t in 0..time_units {
+        if let Some(index) = running {
+            jobs[index].remaining -= 1;
+            ran += 1;
+            if jobs[index].remaining == 0 {
+                jobs[index].finish = Some(t);
+            }
+        }
+
+        for (index, job) in jobs.iter_mut().enumerate() {
+            if job.process.arrival_time == t {
+                log.push(format!("Time {:3} : {} arrived", t, job.process.name));
+                job.joined = t;
+                queue.push(index);
+            }
+        }
+
+        if let (Reference::Rr(quantum), Some(index)) = (reference, running) {
+            if jobs[index].finish.is_none() && ran == quantum {
+                jobs[index].joined = t;
+                queue.push(index);
+                running = None;
+            }
+        }
+
+        if let Some(index) = running.filter(|index| jobs[*index].finish.is_some()) {
+            log.push(format!("Time {:3} : {} finished", t, jobs[index].process.name));
+            running = None;
+        }
+
+        let next = match reference {
+            Reference::Fcfs if running.is_none() => queue.iter().copied()
+                .min_by_key(|i| (jobs[*i].joined, jobs[*i].process.arrival_time, &jobs[*i].process.name)),
+            Reference::RealSjf if running.is_none() => queue.iter().copied()
+                .min_by_key(|i| (jobs[*i].remaining, &jobs[*i].process.name)),
+            Reference::Rr(_) if running.is_none() => queue.first().copied(),
+            // the running job only loses the cpu to one with strictly less left
+            Reference::Sjf => queue.iter().copied()
+                .min_by_key(|i| (jobs[*i].remaining, jobs[*i].process.arrival_time, &jobs[*i].process.name))
+                .filter(|i| running.is_none_or(|r| jobs[*i].remaining < jobs[r].remaining)),
+            _ => None,
+        };
+
+        if let Some(index) = next {
+            queue.retain(|i| *i != index);
+            if let Some(preempted) = running {
+                queue.push(preempted);
+            }
+
+            running = Some(index);
+            ran = 0;
+            jobs[index].first_run.get_or_insert(t);
+            log.push(format!("Time {:3} : {} selected (burst {:3})", t, jobs[index].process.name, jobs[index].remaining));
+        }
+
+        if running.is_none() {
+            log.push(format!("Time {:3} : Idle", t));
+        }
+    }
+
+    log.push(format!("Finished at time {:3}", time_units));
+    log.push(String::new());
+
+    for job in &jobs {
+        let process = job.process;
+        match (job.finish, job.first_run) {
+            (Some(finish), Some(first_run)) => {
+                let turnaround = finish - process.arrival_time;
+                log.push(format!("{} wait {:3} turnaround {:3} response {:3}",
+                    process.name, turnaround - process.burst_time, turnaround, first_run - process.arrival_time));
+            },
+            _ => log.push(format!("{} did not finish", process.name)),
+        }
+    }
+
+    Ok(log)
+}
+
+/// The first line where the logs differ, if they do
+pub fn first_divergence(expected: &[String], actual: &[String]) -> Option<Divergence> {
+    let line = (0..expected.len().max(actual.len())).find(|i| expected.get(*i) != actual.get(*i))?;
+
+    Some(Divergence::at(line, expected.get(line).cloned(), actual.get(line).cloned()))
+}
+
+/// Holds each line the engine writes against the reference's as it goes, so only the first difference is kept
+struct Against {
+    expected: Rc<Vec<String>>,
+    written: usize,
+    divergence: Rc<RefCell<Option<Divergence>>>,
+}
+
+impl Sink for Against {
+    fn write_line(&mut self, line: fmt::Arguments) {
+        self.written += 1;
+
+        // the header names the scheduler, which the reference has no say in
+        let Some(index) = self.written.checked_sub(3) else {
+            return;
+        };
+        let mut divergence = self.divergence.borrow_mut();
+        if divergence.is_some() {
+            return;
+        }
+
+        let line = line.to_string();
+        if self.expected.get(index) != Some(&line) {
+            *divergence = Some(Divergence::at(index, self.expected.get(index).cloned(), Some(line)));
+        }
+    }
+
+    fn line_count(&self) -> usize {
+        self.written
+    }
+}
+
+/// Runs the processes through both the reference and the engine, returning where their logs first differ
+pub fn difftest(reference: Reference, processes: &[Process], time_units: i32) -> Result<Option<Divergence>, Box<dyn Error>> {
+    let expected = Rc::new(simulate(reference, processes, time_units)?);
+    let divergence = Rc::new(RefCell::new(None));
+
+    let scheduler = build_scheduler(reference.keyword(), &reference.params())?;
+    let mut engine = model(processes.to_vec(), scheduler, time_units)?;
+    let mut cpu = CPU::with_sink(Box::new(Against { expected: expected.clone(), written: 0, divergence: divergence.clone() }));
+    cpu.run(&mut engine);
+
+    if let Some(divergence) = divergence.take() {
+        return Ok(Some(divergence));
+    }
+
+    // every line the engine wrote matched, but it may have stopped short
+    let written = cpu.line_count().saturating_sub(2);
+    Ok(expected.get(written).map(|line| Divergence::at(written, Some(line.to_owned()), None)))
+}
@@ -0,0 +1,128 @@
+// every scheduler a `use` line can pick, the built in ones and any a library user registers
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::{
+    custom::Custom,
+    fcfs::FCFS,
+    mlq::Mlq,
+    real_sjf::RealSjf,
+    rr::RR,
+    scheduler::Scheduler,
+    sjf::SJF,
+    sjf_predict::SjfPredict,
+};
+
+/// Builds a scheduler from the named parameters of its `use` line or scheduler table
+pub type BuildFn = fn(&HashMap<String, Value>) -> Result<Box<dyn Scheduler>, Box<dyn Error>>;
+
+/// Rebuilds a scheduler from the state its `save_state` gave, when a checkpoint is loaded
+pub type RestoreFn = fn(Value) -> Result<Box<dyn Scheduler>, Box<dyn Error>>;
+
+/// Everything needed to pick a scheduler by name
+#[derive(Clone)]
+pub struct Registration {
+    /// The `use` keyword, the same one the scheduler's `keyword` returns so a checkpoint can find it again
+    pub keyword: &'static str,
+    /// A short description for listing the schedulers
+    pub description: &'static str,
+    /// The parameters the scheduler takes, any other is an error before `build` is called
+    pub params: &'static [&'static str],
+    pub build: BuildFn,
+    pub restore: RestoreFn,
+}
+
+static REGISTRY: OnceLock<RwLock<Vec<Registration>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<Vec<Registration>> {
+    REGISTRY.get_or_init(|| RwLock::new(vec![
+        FCFS::registration(),
+        SJF::registration(),
+        RealSjf::registration(),
+        SjfPredict::registration(),
+        RR::registration(),
+        Mlq::registration(),
+        Custom::registration(),
+    ]))
+}
+
+/// Makes a scheduler available to every `use` line read from now on
+pub fn register(registration: Registration) -> Result<(), Box<dyn Error>> {
+    let mut registrations = registry().write().unwrap_or_else(PoisonError::into_inner);
+    if registrations.iter().any(|r| r.keyword == registration.keyword) {
+        return Err(format!("a scheduler called `{}` is already registered", registration.keyword).into());
+    }
+
+    registrations.push(registration);
+    Ok(())
+}
+
+/// Every registered scheduler, the built in ones first
+pub fn registrations() -> Vec<Registration> {
+    registry().read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+fn find(keyword: &str) -> Option<Registration> {
+    registry().read().unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|r| r.keyword == keyword)
+        .cloned()
+}
+
+/// Builds the scheduler picked by the given `use` keyword from its named parameters
+pub fn build_scheduler(name: &str, params: &HashMap<String, Value>) -> Result<Box<dyn Scheduler>, Box<dyn Error>> {
+    let registration = find(name).ok_or_else(|| format!("unknown scheduler `{}`", name))?;
+
+    if let Some(unknown) = params.keys().find(|key| !registration.params.contains(&key.as_str())) {
+        return Err(format!("scheduler `{}` does not take a `{}` parameter", name, unknown).into());
+    }
+
+    (registration.build)(params)
+}
+
+/// Rebuilds a scheduler from the keyword and state saved in a checkpoint
+pub fn restore_scheduler(keyword: &str, state: Value) -> Result<Box<dyn Scheduler>, Box<dyn Error>> {
+    let registration = find(keyword).ok_or_else(|| format!("unknown scheduler `{}` in checkpoint", keyword))?;
+    (registration.restore)(state)
+}
+
+/// A `RestoreFn` for any scheduler whose saved state is itself serialized
+pub fn restore_as<T: Scheduler + DeserializeOwned + 'static>(state: Value) -> Result<Box<dyn Scheduler>, Box<dyn Error>> {
+    Ok(Box::new(serde_json::from_value::<T>(state)?))
+}
+
+/// A whole number parameter that has to be given
+pub fn int_param(params: &HashMap<String, Value>, key: &str) -> Result<i32, Box<dyn Error>> {
+    let value = params.get(key).ok_or_else(|| format!("missing `{}` parameter", key))?;
+
+    match value.as_i64().map(i32::try_from) {
+        Some(Ok(number)) => Ok(number),
+        _ => Err(format!("`{}` must be a whole number, found {}", key, value).into()),
+    }
+}
+
+/// A number parameter that can be left out, whole or not
+pub fn float_param(params: &HashMap<String, Value>, key: &str, default: f64) -> Result<f64, Box<dyn Error>> {
+    match params.get(key) {
+        None => Ok(default),
+        Some(value) => value.as_f64().ok_or_else(|| format!("`{}` must be a number, found {}", key, value).into()),
+    }
+}
+
+/// The optional `aging N` parameter, how many ticks of waiting lower a process's key by one
+pub fn aging_param(params: &HashMap<String, Value>) -> Result<Option<i32>, Box<dyn Error>> {
+    if !params.contains_key("aging") {
+        return Ok(None);
+    }
+
+    let aging = int_param(params, "aging")?;
+    if aging < 1 {
+        return Err("aging must be at least 1".into());
+    }
+    Ok(Some(aging))
+}
@@ -0,0 +1,128 @@
+use std::io::{Result, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use super::{
+    cpu::CPU,
+    process::Process,
+    schedule::ScheduleModel,
+    timeline::TickRow,
+};
+
+/// The ways the results of a run can be written out
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum Format {
+    /// The log the graders expect
+    Text,
+    /// The log and every process's results as one JSON document
+    Json,
+    /// A row of results per process
+    Csv,
+}
+
+#[derive(Serialize)]
+struct RunReport<'a> {
+    processcount: i32,
+    runfor: i32,
+    scheduler: String,
+    processes: Vec<ProcessReport<'a>>,
+    log: &'a [String],
+}
+
+#[derive(Serialize)]
+struct ProcessReport<'a> {
+    name: &'a str,
+    arrival: i32,
+    burst: i32,
+    finished: bool,
+    finish_time: Option<i32>,
+    wait: i32,
+    turnaround: i32,
+    response: i32,
+    preemptions: i32,
+    selections: i32,
+    slices: i32,
+    max_ready_streak: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    release_time: Option<i32>,
+}
+
+impl<'a> From<&'a Process> for ProcessReport<'a> {
+    fn from(process: &'a Process) -> Self {
+        ProcessReport {
+            name: &process.name,
+            arrival: process.arrival_time,
+            burst: process.burst_time,
+            finished: process.finished(),
+            finish_time: process.finished().then_some(process.finish_time),
+            wait: process.wait_time,
+            turnaround: process.turnaround_time,
+            response: process.response_time,
+            preemptions: process.preemptions,
+            selections: process.selections,
+            slices: process.slices,
+            max_ready_streak: process.max_ready_streak,
+            release_time: process.release_time,
+        }
+    }
+}
+
+/// Writes the results of a finished run in the given format
+pub fn write_report(out: &mut dyn Write, format: Format, cpu: &CPU, model: &ScheduleModel) -> Result<()> {
+    match format {
+        Format::Text => cpu.write_output(out),
+        Format::Json => {
+            let report = RunReport {
+                processcount: model.number_of_processes,
+                runfor: model.time_units,
+                scheduler: model.scheduler.keyword(),
+                processes: model.process_list.iter().map(ProcessReport::from).collect(),
+                log: cpu.output(),
+            };
+
+            serde_json::to_writer_pretty(&mut *out, &report)?;
+            writeln!(out)
+        },
+        Format::Csv => write_process_csv(out, &model.process_list),
+    }
+}
+
+/// One row per process with its results, blank finish time if it did not finish
+pub fn write_process_csv(out: &mut dyn Write, processes: &[Process]) -> Result<()> {
+    writeln!(out, "name,arrival,burst,finish_time,wait,turnaround,response,preemptions,selections,slices,finished")?;
+
+    for process in processes {
+        let finish_time = if process.finished() { process.finish_time.to_string() } else { String::new() };
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            csv_field(&process.name), process.arrival_time, process.burst_time, finish_time,
+            process.wait_time, process.turnaround_time, process.response_time,
+            process.preemptions, process.selections, process.slices, process.finished()
+        )?;
+    }
+
+    Ok(())
+}
+
+/// One row per tick with the process that runs next and how many are waiting behind it, blank when idle
+pub fn write_timeline_csv(out: &mut dyn Write, rows: &[TickRow]) -> Result<()> {
+    writeln!(out, "time,running,ready")?;
+
+    for row in rows {
+        writeln!(out, "{},{},{}", row.time, csv_field(row.running.as_deref().unwrap_or_default()), row.ready)?;
+    }
+
+    Ok(())
+}
+
+/// Quotes a field if it would otherwise break the row apart
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    }
+    else {
+        field.to_string()
+    }
+}
@@ -1,9 +1,13 @@
 use std::collections::VecDeque;
 
 use super::process::Process;
+use serde::{Deserialize, Serialize};
+
+use super::registry::{int_param, restore_as, Registration};
 use super::scheduler::Scheduler;
 
 /// Struct representing the Round-Robin scheduler
+#[derive(Clone, Serialize, Deserialize)]
 pub struct RR {
     quantum: i32,
     quantum_remaining: i32,
@@ -13,6 +17,23 @@ pub struct RR {
 }
 
 impl RR {
+    /// How `use rr` builds the scheduler
+    pub fn registration() -> Registration {
+        Registration {
+            keyword: "rr",
+            description: "Round-Robin, each process runs for at most `quantum` ticks before going to the back of the queue",
+            params: &["quantum"],
+            build: |params| {
+                let quantum = int_param(params, "quantum")?;
+                if quantum < 1 {
+                    return Err("quantum must be at least 1".into());
+                }
+                Ok(Box::new(RR::new(quantum)))
+            },
+            restore: restore_as::<RR>,
+        }
+    }
+
     pub fn new(quantum: i32) -> RR {
         RR {
             quantum,
@@ -63,9 +84,22 @@ impl Scheduler for RR {
     }
 
     fn selected_process_name(&self) -> Option<String> {
-        match &self.selected_process {
-            Some(name) => Some(name.to_string()),
-            None => None
-        }
+        self.selected_process.clone()
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        self.process_queue.iter().cloned().collect()
+    }
+
+    fn keyword(&self) -> String {
+        "rr".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
     }
 }
\ No newline at end of file
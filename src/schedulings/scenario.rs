@@ -0,0 +1,124 @@
+// structured scenario files, an alternative to the whitespace `.in` format
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{
+    process::{pick_bursts, Process},
+    registry::build_scheduler,
+    schedule::{check_processes, read_contents, validate_processes, ScheduleModel},
+};
+
+/// A scenario as written in a TOML or JSON file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub runfor: i32,
+    /// Defaults to the number of processes listed
+    pub processcount: Option<i32>,
+    pub scheduler: SchedulerConfig,
+    pub processes: Vec<ProcessConfig>,
+    #[serde(default)]
+    pub output: OutputOptions,
+}
+
+#[derive(Deserialize)]
+pub struct SchedulerConfig {
+    /// The same keyword as the `use` line of an `.in` file
+    #[serde(rename = "use")]
+    pub name: String,
+    /// Everything else in the table, such as `quantum`
+    #[serde(flatten)]
+    pub params: HashMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessConfig {
+    pub name: String,
+    pub arrival: i32,
+    pub burst: Option<i32>,
+    /// Separate cpu bursts, in place of a single `burst`
+    pub bursts: Option<Vec<i32>>,
+    /// Ticks of I/O after each burst but the last
+    #[serde(default)]
+    pub io: i32,
+    /// The `mlq` class the process belongs to
+    pub class: Option<String>,
+    /// The processes that have to finish before this one is released
+    #[serde(default)]
+    pub after: Vec<String>,
+}
+
+/// Where and how the results of a run are written
+#[derive(Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OutputOptions {
+    /// Overrides the default of the input path with an `.out` extension, relative to the scenario file's directory
+    pub path: Option<String>,
+}
+
+impl Scenario {
+    pub fn from_toml(contents: &str) -> Result<Scenario, Box<dyn Error>> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    pub fn from_json(contents: &str) -> Result<Scenario, Box<dyn Error>> {
+        Ok(serde_json::from_str(contents)?)
+    }
+
+    /// Converts the scenario into the same model an `.in` file produces
+    pub fn into_model(self) -> Result<ScheduleModel, Box<dyn Error>> {
+        let process_list = self.processes.into_iter()
+            .map(|p| {
+                let bursts = pick_bursts(p.burst, p.bursts).map_err(|e| format!("{}: {}", p.name, e))?;
+                let mut process = Process::with_bursts(p.name, p.arrival, bursts);
+                process.io = p.io;
+                process.class = p.class;
+                process.after = p.after;
+                Ok(process)
+            })
+            .collect::<Result<Vec<Process>, Box<dyn Error>>>()?;
+        validate_processes(&process_list)?;
+
+        let scheduler = build_scheduler(&self.scheduler.name, &self.scheduler.params)?;
+        check_processes(scheduler.as_ref(), &process_list)?;
+
+        Ok(ScheduleModel {
+            number_of_processes: self.processcount.unwrap_or(process_list.len() as i32),
+            time_units: self.runfor,
+            scheduler,
+            process_list,
+        })
+    }
+}
+
+/// Reads a scenario from any supported format, picked by the file extension (`.toml`, `.json`, anything else is read as `.in`)
+/// A path of `-` reads an `.in` scenario from stdin
+pub fn read_file(file_path: &str) -> Result<(ScheduleModel, OutputOptions), Box<dyn Error>> {
+    if file_path == "-" {
+        return Ok((read_contents(io::stdin().lock())?, OutputOptions::default()));
+    }
+
+    let scenario = match Path::new(file_path).extension().and_then(|e| e.to_str()) {
+        Some("toml") => Scenario::from_toml(&fs::read_to_string(file_path)?)?,
+        Some("json") => Scenario::from_json(&fs::read_to_string(file_path)?)?,
+        _ => return Ok((read_contents(File::open(file_path)?)?, OutputOptions::default())),
+    };
+
+    let mut output = scenario.output.clone();
+    // the path is written next to the scenario, not in whatever directory it was run from
+    if let Some(path) = output.path.as_mut().filter(|path| path.as_str() != "-") {
+        if let Some(dir) = Path::new(file_path).parent() {
+            *path = dir.join(&*path).to_string_lossy().into_owned();
+        }
+    }
+
+    Ok((scenario.into_model()?, output))
+}
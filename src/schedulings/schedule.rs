@@ -1,20 +1,19 @@
 // umbrella for all schedule algorithms
 
-use core::panic;
+use std::collections::HashMap;
 use std::error::Error;
-use std::fs::File; 
-use std::io::{BufReader, BufRead, Lines};
+use std::io::{BufReader, BufRead, Read};
+
+use serde_json::Value;
 
 use super::{
-    process::Process, 
-    fcfs::FCFS, 
-    real_sjf::RealSjf, 
-    sjf::SJF,
-    rr::RR, 
-    scheduler::Scheduler, 
+    process::Process,
+    registry::build_scheduler,
+    scheduler::Scheduler,
 };
 
 
+#[derive(Clone)]
 pub struct ScheduleModel {
     pub number_of_processes: i32,
     pub time_units: i32,
@@ -22,113 +21,266 @@ pub struct ScheduleModel {
     pub process_list: Vec<Process>
 }
 
-pub fn read_contents(input_file: File) -> Result<ScheduleModel, Box<dyn Error>> {
+pub fn read_contents(input: impl Read) -> Result<ScheduleModel, Box<dyn Error>> {
     // Create a BufReader to efficiently read lines
-    let reader: BufReader<File> = BufReader::new(input_file);
+    let reader = BufReader::new(input);
 
-    // creates an iterator for the lines of text in the file
-    let mut lines_iter: Lines<BufReader<File>> = reader.lines();
+    let mut contents = InFile::default();
+    let mut end_flag = false;
 
-    let mut schedule_model = ScheduleModel {
-        number_of_processes: 0,
-        time_units: 0,
-        scheduler: Box::new(FCFS::default()),
-        process_list: vec![],
-    };
+    for (index, line) in reader.lines().enumerate() {
+        // uses ? to extract the string value from the Result<String, Error> type in line variable
+        let line = line?;
+        let tokens = tokenize(&line);
+
+        // blank and comment only lines have no tokens
+        let Some((&keyword, values)) = tokens.split_first() else {
+            continue;
+        };
 
-    // reads first line for process count
-    if let Some(Ok(first_line)) = lines_iter.next() {
-        // makes sure we're actually getting process count
-        if !(first_line.split_whitespace().nth(0).unwrap_or_default() == "processcount") {
-            panic!("failed to get process count");
+        if keyword == "end" {
+            end_flag = true;
+            break;
         }
 
-        // gets the number of processes
-        schedule_model.number_of_processes = first_line.split_whitespace().nth(1).unwrap_or_default().parse::<i32>()?;
-        
-    } else {
-        panic!("no first line found")
+        contents.read_line(keyword, values)
+            .map_err(|e| format!("line {}: {}", index + 1, e))?;
     }
 
-    // get runfor next
-    if let Some(Ok(second_line)) = lines_iter.next() {
+    if !end_flag {
+        return Err("no end stated".into());
+    }
+
+    contents.into_model()
+}
+
+/// Splits a line into whitespace separated tokens, dropping everything after a `#`
+fn tokenize(line: &str) -> Vec<&str> {
+    line.split('#').next().unwrap_or_default().split_whitespace().collect()
+}
+
+/// The parts of an `.in` file read so far, since header lines can come in any order
+#[derive(Default)]
+struct InFile {
+    number_of_processes: Option<i32>,
+    time_units: Option<i32>,
+    scheduler_name: Option<String>,
+    scheduler_params: HashMap<String, Value>,
+    /// The `class` lines of an `mlq` scheduler, highest priority first
+    classes: Vec<Value>,
+    process_list: Vec<Process>,
+}
+
+impl InFile {
+    fn read_line(&mut self, keyword: &str, values: &[&str]) -> Result<(), Box<dyn Error>> {
+        match keyword {
+            "process" => self.process_list.push(Process::parse(values)?),
+            "class" => self.classes.push(class_table(values)?),
+            "processcount" => set_once(&mut self.number_of_processes, keyword, single_int(keyword, values)?)?,
+            "runfor" => set_once(&mut self.time_units, keyword, single_int(keyword, values)?)?,
+            "use" => {
+                let (name, params) = values.split_first().ok_or("`use` needs a scheduler name")?;
+                set_once(&mut self.scheduler_name, keyword, name.to_string())?;
 
-        // makes sure we're actually getting process count
-        if !(second_line.split_whitespace().nth(0).unwrap_or_default() == "runfor") {
-            panic!("failed to get runfor");
+                // parameters can also follow the name, as in `use rr quantum 3`
+                let mut rest = params;
+                while let [key, values @ ..] = rest {
+                    if values.first() == Some(&"=") {
+                        self.read_param(key, values)?;
+                        break;
+                    }
+                    self.read_param(key, &values[..values.len().min(1)])?;
+                    rest = &values[values.len().min(1)..];
+                }
+            },
+            // any other header is a parameter for the scheduler, such as `quantum 3`
+            _ => self.read_param(keyword, values)?,
         }
 
-        schedule_model.time_units = second_line.split_whitespace().nth(1).unwrap_or_default().parse::<i32>()?;
-        
-    } else {
-        panic!("no second line found")
+        Ok(())
     }
 
-    schedule_model.scheduler = parse_scheduler(&mut lines_iter)?;
+    fn read_param(&mut self, key: &str, values: &[&str]) -> Result<(), Box<dyn Error>> {
+        // `key = ...` takes the rest of the line as text, as in `score = remaining + wait / 4`
+        let value = match values {
+            ["=", text @ ..] if !text.is_empty() => Value::from(text.join(" ")),
+            [value] => param_value(value),
+            _ => return Err(format!("`{}` needs exactly one value", key).into()),
+        };
 
-    let mut end_flag = false;
+        if self.scheduler_params.insert(key.to_string(), value).is_some() {
+            return Err(format!("`{}` is given more than once", key).into());
+        }
 
-    // // Iterate over the rest of the lines and process each line
-    for line in lines_iter {
+        Ok(())
+    }
 
-        // uses ? to extract the string value from the Result<String, Error> type in line variable
-        let line = line?;
-        
-        let mut process_line = line.split_whitespace();
+    fn into_model(mut self) -> Result<ScheduleModel, Box<dyn Error>> {
+        let scheduler_name = self.scheduler_name.ok_or("no `use` line found")?;
+        validate_processes(&self.process_list)?;
 
-        // we only need to read the first line to know what action to take so we'll use a reference cause we don't want to waste memory on cloning
-        let first_word = process_line.nth(0).expect("No lines");
-        
-        if first_word == "end" {
-            end_flag = true;
-            break;
+        // the same shape as the `classes` array of a TOML or JSON scheduler table
+        if !self.classes.is_empty() && self.scheduler_params.insert("classes".to_string(), Value::from(self.classes)).is_some() {
+            return Err("`classes` is given more than once".into());
         }
-        else if first_word != "process" {
-            panic!("improper formatting or out of place line")
+
+        let scheduler = build_scheduler(&scheduler_name, &self.scheduler_params)?;
+        check_processes(scheduler.as_ref(), &self.process_list)?;
+
+        Ok(ScheduleModel {
+            number_of_processes: self.number_of_processes.unwrap_or(self.process_list.len() as i32),
+            time_units: self.time_units.ok_or("no `runfor` line found")?,
+            scheduler,
+            process_list: self.process_list,
+        })
+    }
+}
+
+/// Reads `class NAME SCHEDULER [key value]...` into a table of the class's name, scheduler and parameters
+fn class_table(values: &[&str]) -> Result<Value, Box<dyn Error>> {
+    let [name, keyword, params @ ..] = values else {
+        return Err("`class` needs a name and a scheduler, as in `class batch fcfs`".into());
+    };
+
+    let mut table = serde_json::Map::new();
+    table.insert("name".to_string(), Value::from(*name));
+    table.insert("use".to_string(), Value::from(*keyword));
+
+    for pair in params.chunks(2) {
+        let [key, value] = pair else {
+            return Err(format!("`{}` is missing a value", pair[0]).into());
+        };
+        if table.insert(key.to_string(), param_value(value)).is_some() {
+            return Err(format!("`{}` is given more than once", key).into());
         }
+    }
+
+    Ok(Value::Object(table))
+}
 
-        let new_process = Process::parse(process_line)?;
-        schedule_model.process_list.push(new_process);
+/// Reads the one whole number that follows a header like `runfor`
+fn single_int(key: &str, values: &[&str]) -> Result<i32, Box<dyn Error>> {
+    match values {
+        [value] => value.parse::<i32>().map_err(|_| format!("`{}` must be a whole number, found `{}`", key, value).into()),
+        _ => Err(format!("`{}` needs exactly one value", key).into()),
     }
+}
 
-    if end_flag == false {
-        panic!("no end stated");
+/// Stores a value that may only be given once in a file
+pub fn set_once<T>(slot: &mut Option<T>, key: &str, value: T) -> Result<(), Box<dyn Error>> {
+    if slot.is_some() {
+        return Err(format!("`{}` is given more than once", key).into());
     }
 
-    Ok(schedule_model)
+    *slot = Some(value);
+    Ok(())
 }
 
+/// Turns a scheduler parameter token into the same kind of value a TOML or JSON file would hold
+fn param_value(token: &str) -> Value {
+    if let Ok(number) = token.parse::<i64>() {
+        return Value::from(number);
+    }
+    if let Ok(number) = token.parse::<f64>() {
+        return Value::from(number);
+    }
 
-/// Reads the next lines to determine the scheduler
-fn parse_scheduler(lines_iter: &mut Lines<BufReader<File>>) -> Result<Box<dyn Scheduler>, Box<dyn Error>> {
+    Value::from(token)
+}
 
-    if let Some(Ok(next_line)) = lines_iter.next() {
-        if !(next_line.split_whitespace().nth(0).unwrap_or_default() == "use") {
-            panic!("incorrect scheduler algorithm line format");
+/// Checks the processes of a model make sense together, whatever format they were read from
+pub fn validate_processes(processes: &[Process]) -> Result<(), Box<dyn Error>> {
+    let mut index_of: HashMap<&str, usize> = HashMap::with_capacity(processes.len());
+
+    for (index, process) in processes.iter().enumerate() {
+        if process.arrival_time < 0 {
+            return Err(format!("process {} arrives before time 0", process.name).into());
+        }
+        if process.bursts.is_empty() || process.bursts.iter().any(|burst| *burst < 1) {
+            return Err(format!("process {} needs a burst of at least 1", process.name).into());
+        }
+        if process.io < 0 {
+            return Err(format!("process {} cannot do less than 0 ticks of I/O", process.name).into());
+        }
+        if index_of.insert(&process.name, index).is_some() {
+            return Err(format!("process name {} is used more than once", process.name).into());
         }
+    }
 
-        let scheduler_name = next_line.split_whitespace().nth(1).unwrap_or_default().to_string();
-        
-        return match scheduler_name.as_str() {
-                "fcfs" => Ok(Box::new(FCFS::default())),
-                "sjf" => Ok(Box::new(SJF::default())),
-                "rr" => parse_rr(lines_iter),
-                "realSJF" => Ok(Box::new(RealSjf::default())),
-                _ => panic!("Scheduler name not found in file!")
+    for process in processes {
+        if let Some(unknown) = process.after.iter().find(|name| !index_of.contains_key(name.as_str())) {
+            return Err(format!("process {} waits on {}, which is not a process", process.name, unknown).into());
         }
     }
 
-    panic!("no `use` line found")
+    if let Some(cycle) = find_cycle(processes, &index_of) {
+        return Err(format!("processes {} wait on each other in a cycle", cycle.join(" -> ")).into());
+    }
+
+    Ok(())
 }
 
-fn parse_rr(lines_iter: &mut Lines<BufReader<File>>) -> Result<Box<dyn Scheduler>, Box<dyn Error>> {
+/// A chain of processes that each wait on the next and end back at the first, if the `after` lists have one
+fn find_cycle(processes: &[Process], index_of: &HashMap<&str, usize>) -> Option<Vec<String>> {
+    let waits_on: Vec<Vec<usize>> = processes.iter()
+        .map(|p| p.after.iter().map(|name| index_of[name.as_str()]).collect())
+        .collect();
 
-    if let Some(Ok(next_line)) = lines_iter.next() {
-        if next_line.split_whitespace().nth(0).unwrap_or_default() == "quantum" {
-            let quantum = next_line.split_whitespace().nth(1).unwrap_or_default().parse::<i32>()?;
-            return Ok(Box::new(RR::new(quantum)));
+    let mut marks = vec![Mark::Unseen; processes.len()];
+    let cycle = (0..processes.len()).find_map(|index| visit(index, &waits_on, &mut marks))?;
+
+    Some(cycle.into_iter().map(|index| processes[index].name.to_owned()).collect())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mark {
+    Unseen,
+    OnPath,
+    NoCycle,
+}
+
+/// Walks everything the process waits on depth first, returning the cycle it runs into.
+/// The path is kept on a stack of its own rather than by recursing, so a long chain of `after`s cannot overflow the real one
+fn visit(start: usize, waits_on: &[Vec<usize>], marks: &mut [Mark]) -> Option<Vec<usize>> {
+    if marks[start] != Mark::Unseen {
+        return None;
+    }
+
+    // each process on the path with how many of the processes it waits on have been walked
+    let mut path: Vec<(usize, usize)> = vec![(start, 0)];
+    marks[start] = Mark::OnPath;
+
+    while let Some((index, walked)) = path.last_mut() {
+        let Some(&next) = waits_on[*index].get(*walked) else {
+            marks[*index] = Mark::NoCycle;
+            path.pop();
+            continue;
+        };
+        *walked += 1;
+
+        match marks[next] {
+            Mark::NoCycle => {},
+            Mark::OnPath => {
+                let start = path.iter().position(|(i, _)| *i == next)?;
+                let mut cycle: Vec<usize> = path[start..].iter().map(|(i, _)| *i).collect();
+                cycle.push(next);
+                return Some(cycle);
+            },
+            Mark::Unseen => {
+                marks[next] = Mark::OnPath;
+                path.push((next, 0));
+            },
         }
     }
 
-    panic!("quantum line not found!");
-}
\ No newline at end of file
+    None
+}
+
+/// Checks every process can run under the scheduler, such as an `mlq` knowing each process's class
+pub fn check_processes(scheduler: &dyn Scheduler, processes: &[Process]) -> Result<(), Box<dyn Error>> {
+    for process in processes {
+        scheduler.check_process(process)?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
+use serde_json::Value;
+
 use super::process::Process;
 
-pub trait Scheduler {
+/// Schedulers are `Send` so independent runs can be spread across threads
+pub trait Scheduler: Send {
     /// Runs when the given process arrives on the cpu at the given time
     fn on_arrive(&mut self, process: &mut Process, time: i32);
 
@@ -13,9 +16,47 @@ pub trait Scheduler {
     /// Runs just after the process ticks into the given time and finishes
     fn on_finish(&mut self, process: &Process, time: i32);
 
+    /// Runs just after the process ticks into the given time and ends a cpu burst with more to follow
+    /// By default the burst finishes like a process would and the process arrives again straight away
+    fn on_burst_end(&mut self, process: &mut Process, time: i32) {
+        self.on_finish(process, time);
+        self.on_arrive(process, time);
+    }
+
     // The name of the scheduler's currently selected process
     fn selected_process_name(&self) -> Option<String>;
 
+    /// The names of the processes waiting behind the selected one, in the order the scheduler would pick them
+    fn ready_queue(&self) -> Vec<String>;
+
     /// The longer name of the scheduler, used when printing outputs
     fn descriptive_name(&self) -> String;
-}
\ No newline at end of file
+
+    /// The `use` keyword that picks this scheduler in an input file
+    fn keyword(&self) -> String;
+
+    /// A copy of the scheduler in a new box, so a whole model can be cloned mid run
+    fn clone_box(&self) -> Box<dyn Scheduler>;
+
+    /// The scheduler's internal state, so a run can be saved to disk mid run
+    fn save_state(&self) -> serde_json::Result<Value>;
+
+    /// Checks a process can run under this scheduler before the run starts, only `mlq` uses process classes
+    fn check_process(&self, process: &Process) -> Result<(), String> {
+        match &process.class {
+            Some(class) => Err(format!("process {} has class {}, but {} does not use classes", process.name, class, self.keyword())),
+            None => Ok(()),
+        }
+    }
+
+    /// Extra lines for the end of the output, after the per process summary
+    fn summary(&self, _processes: &[Process]) -> Vec<String> {
+        vec![]
+    }
+}
+
+impl Clone for Box<dyn Scheduler> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,112 @@
+// where the cpu's log goes as it is written, kept in memory by default or streamed straight out a line at a time
+
+use std::fmt;
+use std::io::{self, BufWriter, Write};
+
+/// Takes the log lines as the cpu writes them
+pub trait Sink {
+    /// Takes one line, without its newline
+    fn write_line(&mut self, line: fmt::Arguments);
+
+    /// How many lines it has taken so far
+    fn line_count(&self) -> usize;
+
+    /// The lines it has kept, which is none of them for a sink that passes them on
+    fn lines(&self) -> &[String] {
+        &[]
+    }
+
+    /// Whether `lines` holds every line taken so far, which anything reading the log back relies on
+    fn keeps_lines(&self) -> bool {
+        false
+    }
+
+    /// Writes out anything still buffered, reporting the first error any write ran into
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keeps every line, for anything that reads the log back once the run is over
+#[derive(Default)]
+pub struct MemorySink {
+    lines: Vec<String>,
+}
+
+impl From<Vec<String>> for MemorySink {
+    fn from(lines: Vec<String>) -> MemorySink {
+        MemorySink { lines }
+    }
+}
+
+impl Sink for MemorySink {
+    fn write_line(&mut self, line: fmt::Arguments) {
+        self.lines.push(line.to_string());
+    }
+
+    fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    fn keeps_lines(&self) -> bool {
+        true
+    }
+}
+
+/// Writes every line out through a buffer as soon as it is logged, so the log never has to fit in memory
+pub struct WriteSink<W: Write> {
+    out: BufWriter<W>,
+    written: usize,
+    /// The first write that failed, every line after it is dropped
+    error: Option<io::Error>,
+}
+
+impl<W: Write> WriteSink<W> {
+    pub fn new(out: W) -> WriteSink<W> {
+        WriteSink { out: BufWriter::new(out), written: 0, error: None }
+    }
+}
+
+impl<W: Write> Sink for WriteSink<W> {
+    fn write_line(&mut self, line: fmt::Arguments) {
+        self.written += 1;
+
+        if self.error.is_none() {
+            if let Err(e) = writeln!(self.out, "{}", line) {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    fn line_count(&self) -> usize {
+        self.written
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+
+        self.out.flush()
+    }
+}
+
+/// Only counts the lines, for runs where nothing reads the log
+#[derive(Default)]
+pub struct DiscardSink {
+    written: usize,
+}
+
+impl Sink for DiscardSink {
+    fn write_line(&mut self, _line: fmt::Arguments) {
+        self.written += 1;
+    }
+
+    fn line_count(&self) -> usize {
+        self.written
+    }
+}
@@ -1,61 +1,80 @@
 use std::collections::HashMap;
 
 use super::process::{Process, ProcessState};
+use serde::{Deserialize, Serialize};
+
+use super::registry::{aging_param, restore_as, Registration};
 use super::scheduler::Scheduler;
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct SJF {
-    /// A hashmap that contains keys that represent the process name and a integer for time remaining
+    /// A hashmap that contains keys that represent the process name and a integer for time remaining in its current burst
     pub job_list: HashMap<String, (i32, i32)>,
     pub shortest_job: Option<String>,
+    /// Lowers the key of a waiting process by one for every this many ticks it has waited since it last ran
+    #[serde(default)]
+    pub aging: Option<i32>,
+    /// The last time a job was picked, so it only happens once a tick
+    #[serde(default)]
+    pub picked_at: Option<i32>,
 }
 
 impl SJF {
-    /// Iterate through the job_list to find the one with the shortest value
-    /// Sets the shortest_job to the key of that pair
+    /// How `use sjf` builds the scheduler
+    pub fn registration() -> Registration {
+        Registration {
+            keyword: "sjf",
+            description: "preemptive Shortest Job First, always runs the process with the least time left",
+            params: &["aging"],
+            build: |params| Ok(Box::new(SJF { aging: aging_param(params)?, ..SJF::default() })),
+            restore: restore_as::<SJF>,
+        }
+    }
+
+    /// Iterate through the job_list to find the one with the shortest value, ties going to the earliest arrival and then the name
+    /// Sets the shortest_job to the key of that pair, unless the selected one is no longer than it
     fn find_shortest_job(&mut self) {
-        let mut shortest = ("", &i32::MAX);
-        let mut shortest_arrival = i32::MAX;
+        let shortest = self.job_list.iter().min_by_key(|(name, (time_left, arrival_time))| (*time_left, *arrival_time, *name));
 
-        for (name, (time_left, arrival_time)) in self.job_list.iter() {
-            if shortest.1 > time_left || (shortest.1 == time_left && *arrival_time < shortest_arrival) {
-                shortest = (name, time_left);
-                shortest_arrival = *arrival_time;
+        // a waiting process only takes over once it is strictly shorter than the selected one
+        if let (Some(selected), Some((_, shortest))) = (self.shortest_job.as_ref().and_then(|name| self.job_list.get(name)), shortest) {
+            if selected.0 <= shortest.0 {
+                return;
             }
         }
 
-        if shortest.0 == "" {
-            self.shortest_job = None;
-        }
-        else {
-            self.shortest_job = Some(shortest.0.to_string())
-        }
+        self.shortest_job = shortest.map(|(name, _)| name.to_owned());
     }
 }
 
 impl Scheduler for SJF {
     fn descriptive_name(&self) -> String {
-        "preemptive Shortest Job First".to_string()
+        match self.aging {
+            Some(aging) => format!("preemptive Shortest Job First with aging every {} ticks", aging),
+            None => "preemptive Shortest Job First".to_string(),
+        }
     }
     
-    fn on_arrive(&mut self, process: &mut Process, time: i32) {
-        self.job_list.insert(process.name.to_owned(), (process.time_remaining, process.arrival_time));
-
-        if let Some(shortest_job_name) = &self.shortest_job {
-            if self.job_list[shortest_job_name].0 <= process.time_remaining {
-                return;
-            }
-        }
-
-        self.shortest_job = Some(process.name.to_owned());
-        process.select(time);
+    fn on_arrive(&mut self, process: &mut Process, _: i32) {
+        self.job_list.insert(process.name.to_owned(), (process.burst_remaining(), process.arrival_time));
     }
 
     fn on_tick(&mut self, process: &mut Process, _: i32) {
-        self.job_list.insert(process.name.to_owned(), (process.time_remaining, process.arrival_time));
+        // only a waiting process ages, by how long it has waited since it last ran, so the discount is gone once it gets the cpu
+        let aged = match (self.aging, &process.state) {
+            (Some(aging), Some(ProcessState::Ready)) => process.ready_streak / aging,
+            _ => 0,
+        };
+        self.job_list.insert(process.name.to_owned(), (process.burst_remaining() - aged, process.arrival_time));
     }
 
     fn pre_tick(&mut self, process: &mut Process, time: i32) {
+        // every arrival is in and every key has aged by now, so pick once for the whole tick
+        if self.picked_at != Some(time) {
+            self.picked_at = Some(time);
+            self.find_shortest_job();
+        }
+
         if let Some(name) = &self.shortest_job {
             if name == &process.name {
                 if process.state != Some(ProcessState::Running) {
@@ -70,13 +89,31 @@ impl Scheduler for SJF {
 
     fn on_finish(&mut self, finished_process: &Process, _: i32) {
         self.job_list.remove(&finished_process.name);
-        self.find_shortest_job();
+        self.shortest_job = None;
     }
 
     fn selected_process_name(&self) -> Option<String> {
-        match &self.shortest_job {
-            Some(job_name) => Some(job_name.to_string()),
-            None => None
-        }
+        self.shortest_job.clone()
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        let mut waiting: Vec<(&String, &(i32, i32))> = self.job_list.iter()
+            .filter(|(name, _)| self.shortest_job.as_ref() != Some(*name))
+            .collect();
+        waiting.sort_by_key(|(name, (time_left, arrival_time))| (*time_left, *arrival_time, *name));
+
+        waiting.into_iter().map(|(name, _)| name.to_owned()).collect()
+    }
+
+    fn keyword(&self) -> String {
+        "sjf".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
     }
 }
\ No newline at end of file
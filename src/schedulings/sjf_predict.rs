@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::process::{Process, ProcessState};
+use super::registry::{aging_param, float_param, restore_as, Registration};
+use super::scheduler::Scheduler;
+
+/// Non-preemptive Shortest Job First that never looks at burst lengths, guessing each next burst
+/// from the ones the process has already run with exponential averaging
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SjfPredict {
+    /// How much the last burst counts against the older history, between 0 and 1
+    alpha: f64,
+    /// The guess for a process that has not run yet
+    tau0: f64,
+    /// Lowers the prediction of a waiting process by one for every this many ticks it has waited since it last ran
+    aging: Option<i32>,
+
+    /// A hashmap that contains keys that represent the process name and its predicted burst with the time it joined the queue
+    job_list: HashMap<String, (f64, i32)>,
+    /// The next burst predicted for every process seen so far
+    predictions: HashMap<String, f64>,
+    /// The predicted and actual length of every finished burst, per process
+    history: HashMap<String, Vec<(f64, i32)>>,
+
+    selected_process: Option<String>,
+    /// How many ticks the selected process has run for so far
+    ran: i32,
+}
+
+impl SjfPredict {
+    /// How `use sjf-predict` builds the scheduler, `alpha` defaulting to 0.5 and `tau0` to 10
+    pub fn registration() -> Registration {
+        Registration {
+            keyword: "sjf-predict",
+            description: "non-preemptive Shortest Job First that predicts bursts from the ones already run",
+            params: &["alpha", "tau0", "aging"],
+            build: |params| {
+                let alpha = float_param(params, "alpha", 0.5)?;
+                if !(0.0..=1.0).contains(&alpha) {
+                    return Err("alpha must be between 0 and 1".into());
+                }
+                let tau0 = float_param(params, "tau0", 10.0)?;
+                if tau0 < 0.0 {
+                    return Err("tau0 cannot be negative".into());
+                }
+                Ok(Box::new(SjfPredict::new(alpha, tau0, aging_param(params)?)))
+            },
+            restore: restore_as::<SjfPredict>,
+        }
+    }
+
+    pub fn new(alpha: f64, tau0: f64, aging: Option<i32>) -> SjfPredict {
+        SjfPredict {
+            alpha,
+            tau0,
+            aging,
+            job_list: HashMap::new(),
+            predictions: HashMap::new(),
+            history: HashMap::new(),
+            selected_process: None,
+            ran: 0,
+        }
+    }
+
+    /// The waiting processes, shortest predicted burst first, then the longest waiting
+    fn sorted_jobs(&self) -> Vec<(&String, &(f64, i32))> {
+        let mut waiting: Vec<(&String, &(f64, i32))> = self.job_list.iter().collect();
+        waiting.sort_by(|(a_name, (a_tau, a_since)), (b_name, (b_tau, b_since))| {
+            a_tau.total_cmp(b_tau).then(a_since.cmp(b_since)).then(a_name.cmp(b_name))
+        });
+
+        waiting
+    }
+}
+
+impl Scheduler for SjfPredict {
+    fn descriptive_name(&self) -> String {
+        match self.aging {
+            Some(aging) => format!("predictive Shortest Job First (alpha {}, tau0 {}) with aging every {} ticks", self.alpha, self.tau0, aging),
+            None => format!("predictive Shortest Job First (alpha {}, tau0 {})", self.alpha, self.tau0),
+        }
+    }
+
+    fn on_arrive(&mut self, process: &mut Process, time: i32) {
+        let prediction = *self.predictions.entry(process.name.to_owned()).or_insert(self.tau0);
+        self.job_list.insert(process.name.to_owned(), (prediction, time));
+    }
+
+    fn pre_tick(&mut self, process: &mut Process, time: i32) {
+        // the cpu is free, so pick the next job once every arrival for this tick is in
+        if self.selected_process.is_none() {
+            let next = self.sorted_jobs().first().map(|(name, _)| name.to_string());
+            if let Some(name) = next {
+                self.job_list.remove(&name);
+                self.selected_process = Some(name);
+                self.ran = 0;
+            }
+        }
+
+        if self.selected_process.as_ref() == Some(&process.name) {
+            if process.state != Some(ProcessState::Running) {
+                process.select(time);
+            }
+        }
+        else {
+            process.deselect();
+        }
+    }
+
+    fn on_tick(&mut self, process: &mut Process, _: i32) {
+        if self.selected_process.as_ref() == Some(&process.name) {
+            self.ran += 1;
+        }
+
+        let (Some(aging), Some(job)) = (self.aging, self.job_list.get_mut(&process.name)) else {
+            return;
+        };
+
+        let predicted = self.predictions.get(&process.name).copied().unwrap_or(self.tau0);
+        job.0 = predicted - (process.ready_streak / aging) as f64;
+    }
+
+    fn on_finish(&mut self, process: &Process, _: i32) {
+        if self.selected_process.as_ref() != Some(&process.name) {
+            return;
+        }
+
+        // a burst is only seen once it is over, measured by how many ticks the process held the cpu, this last one included
+        let actual = self.ran + 1;
+        let predicted = self.predictions.get(&process.name).copied().unwrap_or(self.tau0);
+
+        self.history.entry(process.name.to_owned()).or_default().push((predicted, actual));
+        self.predictions.insert(process.name.to_owned(), self.alpha * actual as f64 + (1.0 - self.alpha) * predicted);
+        self.selected_process = None;
+    }
+
+    fn selected_process_name(&self) -> Option<String> {
+        self.selected_process.clone()
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        self.sorted_jobs().into_iter().map(|(name, _)| name.to_owned()).collect()
+    }
+
+    fn keyword(&self) -> String {
+        "sjf-predict".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+
+    /// The mean prediction error of every process, with each predicted and actual burst
+    fn summary(&self, processes: &[Process]) -> Vec<String> {
+        let mut lines = vec![String::new(), "Burst prediction error".to_string()];
+
+        for process in processes {
+            let Some(bursts) = self.history.get(&process.name) else {
+                lines.push(format!("{} ran no full bursts", process.name));
+                continue;
+            };
+
+            let error: f64 = bursts.iter().map(|(predicted, actual)| (predicted - *actual as f64).abs()).sum();
+            let predicted: Vec<String> = bursts.iter().map(|(predicted, _)| format!("{:.2}", predicted)).collect();
+            let actual: Vec<String> = bursts.iter().map(|(_, actual)| actual.to_string()).collect();
+
+            lines.push(format!("{} mean error {:6.2} over {:3} bursts (predicted {}, actual {})",
+                process.name, error / bursts.len() as f64, bursts.len(), predicted.join(" "), actual.join(" ")));
+        }
+
+        lines
+    }
+}
@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+use serde::{ser, Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    cpu::CPU,
+    process::Process,
+    registry::restore_scheduler,
+    schedule::ScheduleModel,
+};
+
+/// Everything needed to pick a run back up after a given tick, in a form that can be written to disk
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// The last tick that had already run when the checkpoint was taken
+    pub time: i32,
+    pub number_of_processes: i32,
+    pub time_units: i32,
+    /// The `use` keyword of the scheduler, needed to rebuild it from its state
+    pub scheduler: String,
+    pub scheduler_state: Value,
+    pub process_list: Vec<Process>,
+    pub output: Vec<String>,
+}
+
+impl Checkpoint {
+    /// Takes a checkpoint of a run that has just finished the tick at the given time
+    pub fn capture(cpu: &CPU, model: &ScheduleModel, time: i32) -> serde_json::Result<Checkpoint> {
+        // a checkpoint carries the log so far, which a cpu writing elsewhere no longer has
+        if !cpu.keeps_output() {
+            return Err(ser::Error::custom("a checkpoint needs a cpu that keeps its log in memory"));
+        }
+
+        Ok(Checkpoint {
+            time,
+            number_of_processes: model.number_of_processes,
+            time_units: model.time_units,
+            scheduler: model.scheduler.keyword(),
+            scheduler_state: model.scheduler.save_state()?,
+            process_list: model.process_list.clone(),
+            output: cpu.output().to_vec(),
+        })
+    }
+
+    /// Rebuilds the cpu and model, ready to run from the tick after `time`
+    pub fn restore(self) -> Result<(CPU, ScheduleModel), Box<dyn Error>> {
+        let model = ScheduleModel {
+            number_of_processes: self.number_of_processes,
+            time_units: self.time_units,
+            scheduler: restore_scheduler(&self.scheduler, self.scheduler_state)?,
+            process_list: self.process_list,
+        };
+
+        Ok((CPU::with_output(self.output), model))
+    }
+
+    pub fn save(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let file = File::create(file_path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+
+        Ok(())
+    }
+
+    pub fn load(file_path: &str) -> Result<Checkpoint, Box<dyn Error>> {
+        let file = File::open(file_path)?;
+
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+}
+
+/// Runs the model on the cpu the same way `CPU::run` does, saving a checkpoint to the given path after the tick at `time`
+pub fn run_with_checkpoint(cpu: &mut CPU, model: &mut ScheduleModel, time: i32, file_path: &str) -> Result<(), Box<dyn Error>> {
+    if time < 0 || time >= model.time_units {
+        return Err(format!("checkpoint time {} is outside the run (0 to {})", time, model.time_units - 1).into());
+    }
+
+    cpu.start(model);
+
+    for t in 0..=time {
+        cpu.step(model, t);
+    }
+    Checkpoint::capture(cpu, model, time)?.save(file_path)?;
+
+    cpu.resume(model, time + 1);
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::io::{Error, ErrorKind, Result};
+
+use super::{
+    cpu::CPU,
+    process::{Process, ProcessState},
+    schedule::ScheduleModel,
+    sink::DiscardSink,
+};
+
+/// How many ticks apart the full copies of a model are kept, the ticks in between are replayed from the copy before them
+const KEYFRAME_EVERY: i32 = 64;
+
+/// The state of a run at the end of a single tick, apart from the processes, which are replayed from the keyframes
+#[derive(Clone)]
+pub struct Frame {
+    pub time: i32,
+    /// The process that will run during the next tick
+    pub running: Option<String>,
+    pub ready_queue: Vec<String>,
+    /// The lines the cpu logged during this tick
+    pub events: Vec<String>,
+}
+
+/// Copies of a model every `KEYFRAME_EVERY` ticks, so any tick can be rebuilt without keeping a copy of every one
+#[derive(Default)]
+pub struct Keyframes {
+    /// The model after ticks 0, `KEYFRAME_EVERY`, twice that and so on
+    models: Vec<ScheduleModel>,
+}
+
+impl Keyframes {
+    /// Keeps a copy of the model if the tick it just ran is one to keep, ticks have to be recorded in order
+    pub fn record(&mut self, model: &ScheduleModel, t: i32) {
+        if t % KEYFRAME_EVERY == 0 {
+            self.models.push(model.clone());
+        }
+    }
+
+    /// Forgets the copies from after the given tick, for a run that is rewound to it
+    pub fn truncate(&mut self, t: i32) {
+        self.models.truncate((t / KEYFRAME_EVERY + 1) as usize);
+    }
+
+    /// Rebuilds the model after every tick from `first` to `last`, handing each one to `each`
+    pub fn replay(&self, first: i32, last: i32, mut each: impl FnMut(i32, &ScheduleModel)) {
+        let start = first / KEYFRAME_EVERY * KEYFRAME_EVERY;
+        let mut model = self.models[(first / KEYFRAME_EVERY) as usize].clone();
+        // stepping again writes the same lines as the first time, which were already kept
+        let mut cpu = CPU::with_sink(Box::new(DiscardSink::default()));
+
+        for t in start..=last {
+            if t > start {
+                cpu.step(&mut model, t);
+            }
+            if t >= first {
+                each(t, &model);
+            }
+        }
+    }
+
+    /// The model as it was after the given tick
+    pub fn model_at(&self, t: i32) -> ScheduleModel {
+        let mut found = None;
+        self.replay(t, t, |_, model| found = Some(model.clone()));
+        found.expect("replay always reaches its last tick")
+    }
+}
+
+/// The running process and ready queue length at the end of a single tick
+pub struct TickRow {
+    pub time: i32,
+    pub running: Option<String>,
+    pub ready: usize,
+}
+
+impl TickRow {
+    pub fn capture(model: &ScheduleModel, time: i32) -> TickRow {
+        TickRow {
+            time,
+            running: running_process(model),
+            ready: model.scheduler.ready_queue().len(),
+        }
+    }
+}
+
+/// Every frame of a finished run, used to play the run back after the fact
+pub struct Timeline {
+    pub scheduler_name: String,
+    pub time_units: i32,
+    pub frames: Vec<Frame>,
+    keyframes: Keyframes,
+}
+
+impl Timeline {
+    /// Runs the model on the cpu the same way `CPU::run` does, keeping a frame for every tick
+    pub fn record(cpu: &mut CPU, model: &mut ScheduleModel) -> Result<Timeline> {
+        // every frame holds the lines its tick wrote
+        if !cpu.keeps_output() {
+            return Err(Error::new(ErrorKind::InvalidInput, "recording a timeline needs a cpu that keeps its log in memory"));
+        }
+
+        let mut timeline = Timeline {
+            scheduler_name: model.scheduler.descriptive_name(),
+            time_units: model.time_units,
+            frames: vec![],
+            keyframes: Keyframes::default(),
+        };
+
+        cpu.start(model);
+
+        for t in 0..model.time_units {
+            let written = cpu.output().len();
+            cpu.step(model, t);
+
+            timeline.frames.push(Frame {
+                time: t,
+                running: running_process(model),
+                ready_queue: model.scheduler.ready_queue(),
+                events: cpu.output()[written..].to_vec(),
+            });
+            timeline.keyframes.record(model, t);
+        }
+
+        cpu.end(model);
+        Ok(timeline)
+    }
+
+    /// Every process as it was at the end of the frame
+    pub fn processes_at(&self, index: usize) -> Vec<Process> {
+        self.keyframes.model_at(self.frames[index].time).process_list
+    }
+
+    /// Hands the processes at the end of every frame from `first` to `last` to `each`, in order
+    pub fn replay(&self, first: usize, last: usize, mut each: impl FnMut(&[Process])) {
+        self.keyframes.replay(self.frames[first].time, self.frames[last].time, |_, model| each(&model.process_list));
+    }
+}
+
+/// The process that will run during the next tick
+fn running_process(model: &ScheduleModel) -> Option<String> {
+    model.process_list.iter()
+        .find(|p| p.state == Some(ProcessState::Running))
+        .map(|p| p.name.to_owned())
+}
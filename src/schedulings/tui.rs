@@ -0,0 +1,211 @@
+use std::io::{self, Result, Stdout, Write};
+use std::time::Duration;
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, queue,
+    style::Print,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use super::{
+    process::{Process, ProcessState},
+    timeline::{Frame, Timeline},
+};
+
+/// Playback speeds in ticks per second
+const SPEEDS: [u64; 6] = [1, 2, 4, 8, 16, 32];
+
+/// How many of the most recent log lines the events panel shows
+const EVENT_LINES: usize = 8;
+
+const CONTROLS: &str = "space play/pause  \u{2190}/\u{2192} step  PgUp/PgDn \u{b1}10  Home/End seek  +/- speed  q quit";
+
+/// Full screen playback of a recorded run
+pub struct Player<'a> {
+    timeline: &'a Timeline,
+    /// Index of the frame on screen
+    position: usize,
+    playing: bool,
+    speed: usize,
+}
+
+impl<'a> Player<'a> {
+    pub fn new(timeline: &'a Timeline) -> Player<'a> {
+        Player {
+            timeline,
+            position: 0,
+            playing: true,
+            speed: 2,
+        }
+    }
+
+    /// Takes over the terminal until the user quits
+    pub fn play(&mut self) -> Result<()> {
+        if self.timeline.frames.is_empty() {
+            return Ok(());
+        }
+
+        let mut out = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+
+        let result = self.event_loop(&mut out);
+
+        // always hand the terminal back, even if drawing failed
+        execute!(out, Show, LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+
+        result
+    }
+
+    fn event_loop(&mut self, out: &mut Stdout) -> Result<()> {
+        let last = self.timeline.frames.len() - 1;
+
+        loop {
+            self.render(out)?;
+
+            let timeout = if self.playing {
+                Duration::from_millis(1000 / SPEEDS[self.speed])
+            }
+            else {
+                Duration::from_secs(60)
+            };
+
+            if !event::poll(timeout)? {
+                if self.playing {
+                    self.position = (self.position + 1).min(last);
+                    self.playing = self.position < last;
+                }
+                continue;
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue; // resizes just redraw
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char(' ') => {
+                    // playing from the last frame starts over
+                    if !self.playing && self.position == last {
+                        self.position = 0;
+                    }
+                    self.playing = !self.playing;
+                },
+                KeyCode::Right => self.seek(1),
+                KeyCode::Left => self.seek(-1),
+                KeyCode::PageDown => self.seek(10),
+                KeyCode::PageUp => self.seek(-10),
+                KeyCode::Home => self.position = 0,
+                KeyCode::End => self.position = last,
+                KeyCode::Char('+') | KeyCode::Char('=') => self.speed = (self.speed + 1).min(SPEEDS.len() - 1),
+                KeyCode::Char('-') => self.speed = self.speed.saturating_sub(1),
+                _ => {},
+            }
+        }
+    }
+
+    /// Moves by the given number of frames and pauses playback
+    fn seek(&mut self, by: i64) {
+        let last = self.timeline.frames.len() as i64 - 1;
+        self.position = (self.position as i64 + by).clamp(0, last) as usize;
+        self.playing = false;
+    }
+
+    fn render(&self, out: &mut Stdout) -> Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let frame = &self.timeline.frames[self.position];
+        let processes = self.timeline.processes_at(self.position);
+
+        let mut lines = vec![];
+        let status = if self.playing {
+            format!("playing at {} ticks/s", SPEEDS[self.speed])
+        }
+        else {
+            "paused".to_string()
+        };
+        lines.push(format!("{} | time {:3} / {} | {}", self.timeline.scheduler_name.split_whitespace().collect::<Vec<_>>().join(" "), frame.time, self.timeline.time_units, status));
+        lines.push(String::new());
+
+        lines.extend(self.gantt(columns as usize, &processes));
+        lines.push(String::new());
+
+        lines.push(format!("running: {}", frame.running.as_deref().unwrap_or("(idle)")));
+        lines.push(format!("ready:   {}", if frame.ready_queue.is_empty() { "(empty)".to_string() } else { frame.ready_queue.join(" ") }));
+        lines.push(String::new());
+
+        lines.extend(metrics(frame, &processes));
+        lines.push(String::new());
+
+        lines.push("events:".to_string());
+        let events: Vec<&String> = self.timeline.frames[..=self.position].iter()
+            .flat_map(|f| f.events.iter())
+            .filter(|line| !line.ends_with(": Idle"))
+            .collect();
+        for line in events.iter().skip(events.len().saturating_sub(EVENT_LINES)) {
+            lines.push(format!("  {}", line));
+        }
+
+        queue!(out, Clear(ClearType::All))?;
+        for (row, line) in lines.iter().take(rows.saturating_sub(1) as usize).enumerate() {
+            let line: String = line.chars().take(columns as usize).collect();
+            queue!(out, MoveTo(0, row as u16), Print(line))?;
+        }
+        queue!(out, MoveTo(0, rows.saturating_sub(1)), Print(CONTROLS.chars().take(columns as usize).collect::<String>()))?;
+
+        out.flush()
+    }
+
+    /// One bar per process up to the frame on screen, scrolled so the current tick stays visible
+    fn gantt(&self, columns: usize, names: &[Process]) -> Vec<String> {
+        let label_width = names.iter().map(|p| p.name.len()).max().unwrap_or(0).max(4) + 1;
+
+        let width = columns.saturating_sub(label_width).max(1);
+        let first = (self.position + 1).saturating_sub(width);
+        let frames = &self.timeline.frames[first..=self.position];
+
+        let mut bars = vec![String::new(); names.len()];
+        self.timeline.replay(first, self.position, |processes| {
+            for (bar, process) in bars.iter_mut().zip(processes) {
+                bar.push(match process.state {
+                    Some(ProcessState::Running) => '\u{2588}',
+                    Some(ProcessState::Ready) => '\u{2591}',
+                    Some(ProcessState::Io) => '\u{00b7}',
+                    None => ' ',
+                });
+            }
+        });
+
+        let mut rows: Vec<String> = names.iter().zip(bars)
+            .map(|(process, bar)| format!("{:<label_width$}{}", process.name, bar))
+            .collect();
+
+        // a tick mark every 5 time units under the bars
+        let axis: String = frames.iter()
+            .map(|frame| if frame.time % 5 == 0 { '|' } else { ' ' })
+            .collect();
+        rows.push(format!("{:<label_width$}{}", "time", axis));
+        rows.push(format!("{:<label_width$}{}", "", frames[0].time));
+
+        rows
+    }
+}
+
+/// The counters `Process::tick` keeps for every process at this frame
+fn metrics(frame: &Frame, processes: &[Process]) -> Vec<String> {
+    let mut lines = vec![format!("{:<10} {:<11} {:>9} {:>5} {:>10} {:>8}", "process", "state", "remaining", "wait", "turnaround", "response")];
+
+    for process in processes {
+        lines.push(format!(
+            "{:<10} {:<11} {:>9} {:>5} {:>10} {:>8}",
+            process.name, process.state_name(frame.time), process.time_remaining, process.wait_time, process.turnaround_time, process.response_time
+        ));
+    }
+
+    lines
+}
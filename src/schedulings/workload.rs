@@ -0,0 +1,172 @@
+// random workloads for tests and benchmarks, the same seed always giving the same processes
+
+use std::error::Error;
+
+use serde_json::{json, Value};
+
+use super::process::Process;
+use super::registry::build_scheduler;
+use super::schedule::{check_processes, validate_processes, ScheduleModel};
+use super::scheduler::Scheduler;
+
+/// The classes the `mlq` configurations in `schedulers` sort processes into
+pub const MLQ_CLASSES: [&str; 3] = ["a", "b", "c"];
+
+/// A small xorshift generator, plenty for picking arrivals and bursts and with nothing to depend on
+#[derive(Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // xorshift never leaves zero, so the seed is mixed into a nonzero start
+        Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number from 0 up to but not including the given bound
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound.max(1)
+    }
+
+    /// A number in the inclusive range
+    pub fn between(&mut self, low: i32, high: i32) -> i32 {
+        low + self.below((high - low) as u64 + 1) as i32
+    }
+
+    /// True the given percent of the time
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+}
+
+/// The shape of the workloads to generate
+#[derive(Clone)]
+pub struct WorkloadSpec {
+    pub processes: usize,
+    /// The most ticks between one arrival and the next, 0 has everything arrive at once
+    pub max_gap: i32,
+    pub max_burst: i32,
+    /// The most separate bursts a process has, 1 for plain processes
+    pub max_bursts: usize,
+    /// The chance out of 100 that a process waits on up to two of the processes before it
+    pub dependency_percent: u64,
+    /// The classes to spread the processes over, for `mlq`, none leaves the class unset
+    pub classes: Vec<String>,
+}
+
+impl Default for WorkloadSpec {
+    fn default() -> WorkloadSpec {
+        WorkloadSpec {
+            processes: 10,
+            max_gap: 3,
+            max_burst: 8,
+            max_bursts: 1,
+            dependency_percent: 0,
+            classes: vec![],
+        }
+    }
+}
+
+impl WorkloadSpec {
+    /// The same workload, spread over `MLQ_CLASSES` when it is for `mlq`, which needs every process in a class
+    pub fn for_scheduler(&self, keyword: &str) -> WorkloadSpec {
+        match keyword {
+            "mlq" => WorkloadSpec { classes: MLQ_CLASSES.iter().map(|class| class.to_string()).collect(), ..self.clone() },
+            _ => self.clone(),
+        }
+    }
+
+    /// The processes for the given seed, named P1, P2 and so on in order of arrival
+    pub fn generate(&self, seed: u64) -> Vec<Process> {
+        let mut rng = Rng::new(seed);
+        let mut processes: Vec<Process> = Vec::with_capacity(self.processes);
+        let mut arrival = 0;
+
+        for index in 0..self.processes {
+            if index > 0 {
+                arrival += rng.between(0, self.max_gap);
+            }
+
+            let burst_count = rng.between(1, self.max_bursts.max(1) as i32);
+            let bursts = (0..burst_count).map(|_| rng.between(1, self.max_burst.max(1))).collect();
+            let mut process = Process::with_bursts(format!("P{}", index + 1), arrival, bursts);
+
+            // only waiting on earlier processes keeps the dependencies free of cycles
+            if index > 0 && rng.chance(self.dependency_percent) {
+                for _ in 0..rng.between(1, 2) {
+                    let name = &processes[rng.below(index as u64) as usize].name;
+                    if !process.after.contains(name) {
+                        process.after.push(name.to_owned());
+                    }
+                }
+            }
+
+            if !self.classes.is_empty() {
+                process.class = Some(self.classes[rng.below(self.classes.len() as u64) as usize].to_owned());
+            }
+
+            processes.push(process);
+        }
+
+        processes
+    }
+}
+
+/// Every scheduler, some more than once with different parameters, as the `use` keyword and its parameters
+pub fn schedulers() -> Vec<(&'static str, Value)> {
+    vec![
+        ("fcfs", json!({})),
+        ("sjf", json!({})),
+        ("sjf", json!({ "aging": 2 })),
+        ("realSJF", json!({})),
+        ("realSJF", json!({ "aging": 3 })),
+        ("sjf-predict", json!({})),
+        ("sjf-predict", json!({ "alpha": 0.8, "tau0": 2, "aging": 4 })),
+        ("rr", json!({ "quantum": 1 })),
+        ("rr", json!({ "quantum": 3 })),
+        ("custom", json!({ "score": "remaining preemptive" })),
+        ("custom", json!({ "score": "burst - wait / 2" })),
+        ("mlq", json!({ "policy": "strict", "classes": [
+            { "name": "a", "use": "sjf" },
+            { "name": "b", "use": "rr", "quantum": 2 },
+            { "name": "c", "use": "custom", "score": "arrival" },
+        ] })),
+        ("mlq", json!({ "policy": "slice", "classes": [
+            { "name": "a", "use": "rr", "quantum": 2, "slice": 3 },
+            { "name": "b", "use": "realSJF", "slice": 2 },
+            { "name": "c", "use": "sjf-predict", "slice": 1 },
+        ] })),
+    ]
+}
+
+/// Builds a scheduler from one of the `schedulers`
+pub fn build(keyword: &str, params: &Value) -> Result<Box<dyn Scheduler>, Box<dyn Error>> {
+    build_scheduler(keyword, &serde_json::from_value(params.clone())?)
+}
+
+/// A `runfor` long enough for any scheduler that never leaves the cpu idle with work waiting to finish every process
+pub fn horizon(processes: &[Process]) -> i32 {
+    let last_arrival = processes.iter().map(|p| p.arrival_time).max().unwrap_or_default();
+    let work: i32 = processes.iter().map(|p| p.burst_time).sum();
+
+    last_arrival + work + 1
+}
+
+/// A model running the processes under the scheduler, checked the same way a scenario file is
+pub fn model(processes: Vec<Process>, scheduler: Box<dyn Scheduler>, time_units: i32) -> Result<ScheduleModel, Box<dyn Error>> {
+    validate_processes(&processes)?;
+    check_processes(scheduler.as_ref(), &processes)?;
+
+    Ok(ScheduleModel {
+        number_of_processes: processes.len() as i32,
+        time_units,
+        scheduler,
+        process_list: processes,
+    })
+}
@@ -0,0 +1,25 @@
+// checks that aging gets a long job the cpu without starving the short jobs behind it
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::fairness::FairnessLimits;
+use scheduler_gpt::schedulings::scenario::read_file;
+
+fn log(path: &str) -> Vec<String> {
+    let (mut model, _) = read_file(path).unwrap();
+    let mut cpu = CPU { fairness: Some(FairnessLimits { wait_ratio: 3.0, max_streak: 10 }), ..CPU::default() };
+    cpu.run(&mut model);
+    cpu.output().to_vec()
+}
+
+#[test]
+fn the_aged_job_gets_the_cpu_and_nobody_starves() {
+    let log = log("c9-sjf-aging.in");
+
+    // L has been ready since 1, so by 9 its key of 10 - 8 ties the 3 - 1 of S4, and L arrived first
+    assert!(log.iter().any(|line| line == "Time   9 : L selected (burst  10)"), "{:#?}", log);
+    // its discount goes once it runs, so S4 takes the cpu back instead of waiting out all of L
+    assert!(log.iter().any(|line| line == "Time  10 : S4 selected (burst   3)"), "{:#?}", log);
+
+    assert!(log.iter().any(|line| line == "L longest ready streak   9"), "{:#?}", log);
+    assert_eq!(log.last().unwrap(), "0 of 9 processes starved");
+}
@@ -0,0 +1,49 @@
+// runs the batch subcommand over a directory with a broken scenario and two that name the same output path
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+const GOOD: &str = "runfor 10\nuse fcfs\nprocess name A arrival 0 burst 3\nend\n";
+const BAD: &str = "runfor 10\nuse fcfs\nprocess name A arrival 0 burst three\nend\n";
+
+fn json(scheduler: &str) -> String {
+    format!(
+        r#"{{ "runfor": 10, "scheduler": {{ "use": "{}" }}, "processes": [{{ "name": "A", "arrival": 0, "burst": 3 }}], "output": {{ "path": "same.out" }} }}"#,
+        scheduler
+    )
+}
+
+#[test]
+fn a_batch_runs_past_a_bad_file_and_fails_at_the_end() {
+    let root = env::temp_dir().join(format!("scheduler-gpt-batch-{}", std::process::id()));
+    let scenarios = root.join("scenarios");
+    let elsewhere = root.join("elsewhere");
+    for dir in ["scenarios/a", "scenarios/b", "elsewhere"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    fs::write(scenarios.join("bad.in"), BAD).unwrap();
+    fs::write(scenarios.join("good.in"), GOOD).unwrap();
+    fs::write(scenarios.join("a/run.json"), json("fcfs")).unwrap();
+    fs::write(scenarios.join("b/run.json"), json("sjf")).unwrap();
+
+    // run from another directory, which nothing should be written to
+    let output = Command::new(env!("CARGO_BIN_EXE_scheduler-gpt"))
+        .args(["batch", "../scenarios", "-j", "2"])
+        .current_dir(&elsewhere)
+        .output()
+        .unwrap();
+    let summary = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success(), "{}", summary);
+    assert!(summary.contains("error: line 3: `burst` must be a whole number, found `three`"), "{}", summary);
+    assert!(summary.ends_with("4 runs, 3 ok, 1 failed\n"), "{}", summary);
+
+    assert!(fs::read_to_string(scenarios.join("good.out")).unwrap().contains("Using First-Come First-Served"));
+    assert!(fs::read_to_string(scenarios.join("a/same.out")).unwrap().contains("Using First-Come First-Served"));
+    assert!(fs::read_to_string(scenarios.join("b/same.out")).unwrap().contains("Using preemptive Shortest Job First"));
+    assert!(!scenarios.join("bad.out").exists());
+    assert_eq!(fs::read_dir(&elsewhere).unwrap().count(), 0);
+
+    fs::remove_dir_all(root).unwrap();
+}
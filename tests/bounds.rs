@@ -0,0 +1,86 @@
+// checks the lower bounds with dependencies between processes and with more than one cpu
+
+use scheduler_gpt::schedulings::bounds::LowerBounds;
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::schedule::{read_contents, ScheduleModel};
+
+fn model(processes: &str) -> ScheduleModel {
+    read_contents(format!("runfor 40\nuse fcfs\n{}end", processes).as_bytes()).unwrap()
+}
+
+/// A first, then B and C once it finishes, then D once both have
+const DIAMOND: &str = "process name A arrival 0 burst 3\n\
+    process name B arrival 0 burst 2 after A\n\
+    process name C arrival 0 burst 4 after A\n\
+    process name D arrival 0 burst 1 after B C\n";
+
+/// Four processes with nothing between them, all arriving at once
+const FOUR: &str = "process name W arrival 0 burst 3\n\
+    process name X arrival 0 burst 3\n\
+    process name Y arrival 0 burst 3\n\
+    process name Z arrival 0 burst 3\n";
+
+#[test]
+fn dependencies_run_back_to_back_on_the_critical_path() {
+    let bounds = LowerBounds::of(&model(DIAMOND).process_list, 1);
+
+    // B and C can both start at 3, and D has to wait for the later of them
+    assert_eq!(bounds.earliest_finish, [3, 5, 7, 8]);
+    assert_eq!((bounds.work, bounds.critical_path, bounds.makespan()), (10, 8, 10));
+    assert_eq!(bounds.average_turnaround, (3 + 5 + 7 + 8) as f64 / 4.0);
+}
+
+#[test]
+fn a_dependency_that_arrives_late_starts_from_its_arrival() {
+    let processes = "process name A arrival 0 burst 2\nprocess name B arrival 6 burst 3 after A\n";
+    let bounds = LowerBounds::of(&model(processes).process_list, 1);
+
+    assert_eq!(bounds.earliest_finish, [2, 9]);
+    // turnaround counts from B's arrival at 6, not from A's finish
+    assert_eq!(bounds.average_turnaround, (2 + 3) as f64 / 2.0);
+}
+
+#[test]
+fn more_cpus_share_the_work_but_not_the_critical_path() {
+    let diamond = model(DIAMOND);
+    let two = LowerBounds::of(&diamond.process_list, 2);
+    assert_eq!((two.work, two.critical_path, two.makespan()), (5, 8, 8));
+    assert_eq!(two.earliest_finish, [3, 5, 7, 8]);
+
+    let four = model(FOUR);
+    let makespans: Vec<(i32, i32)> = [1, 2, 3, 4, 5].into_iter()
+        .map(|cores| (cores, LowerBounds::of(&four.process_list, cores).makespan()))
+        .collect();
+    // 12 ticks of work, rounded up per cpu, until the 3 of a single burst is all that is left
+    assert_eq!(makespans, [(1, 12), (2, 6), (3, 4), (4, 3), (5, 3)]);
+}
+
+#[test]
+fn work_arriving_later_is_spread_from_its_arrival() {
+    let processes = "process name A arrival 0 burst 2\n\
+        process name B arrival 4 burst 6\n\
+        process name C arrival 4 burst 6\n\
+        process name D arrival 4 burst 6\n";
+    let bounds = LowerBounds::of(&model(processes).process_list, 2);
+
+    // A is long done by 4, so the bound is the 18 ticks arriving then spread over both cpus, over the 10 of all the work from 0
+    assert_eq!(bounds.work, 13);
+    assert_eq!(bounds.describe(), "lower bounds on 2 cpus: makespan 13 (work 13, critical path 10), average turnaround 5.00");
+}
+
+#[test]
+fn a_single_cpu_run_is_measured_against_the_bound_for_more_cpus() {
+    let mut four = model(FOUR);
+    CPU::default().run(&mut four);
+    let report = LowerBounds::of(&four.process_list, 2).report(&four.process_list);
+
+    assert_eq!(report[1], "lower bounds on 2 cpus: makespan 6 (work 6, critical path 3), average turnaround 3.00");
+    // one cpu can never close the gap to what two could do
+    assert_eq!(report[2], "makespan  12, +6 over the bound");
+    assert_eq!(report[3..], [
+        "W earliest finish   3, finished   3 (+0)",
+        "X earliest finish   3, finished   6 (+3)",
+        "Y earliest finish   3, finished   9 (+6)",
+        "Z earliest finish   3, finished  12 (+9)",
+    ]);
+}
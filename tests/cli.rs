@@ -0,0 +1,119 @@
+// runs the binary the way a user would, checking exit codes, stdin and stdout as `-` and every --format
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use serde_json::Value;
+
+const SCENARIO: &str = "runfor 10\nuse fcfs\nprocess name A arrival 0 burst 3\nprocess name B arrival 1 burst 2\nend\n";
+
+/// Runs the binary with the arguments, feeding it stdin
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_scheduler-gpt"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // a run that fails before reading its input may have closed stdin already
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn stdin_is_read_and_written_to_stdout() {
+    let output = run(&["-"], SCENARIO);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    let log = stdout(&output);
+    assert!(log.starts_with("  2 processes\nUsing First-Come First-Served\n"), "{}", log);
+    assert!(log.ends_with("A wait   0 turnaround   3 response   0\nB wait   2 turnaround   4 response   2\n"), "{}", log);
+}
+
+#[test]
+fn output_goes_to_the_path_given() {
+    let dir = env::temp_dir().join(format!("scheduler-gpt-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("run.in");
+    fs::write(&input, SCENARIO).unwrap();
+
+    // next to the input by default, or wherever -o says
+    let output = run(&[&input.to_string_lossy()], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).is_empty());
+    let written = fs::read_to_string(dir.join("run.out")).unwrap();
+
+    let elsewhere = dir.join("elsewhere.txt");
+    let output = run(&[&input.to_string_lossy(), "-o", &elsewhere.to_string_lossy()], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(fs::read_to_string(&elsewhere).unwrap(), written);
+    assert_eq!(stdout(&run(&["-", "-o", "-"], SCENARIO)), written);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn json_holds_the_log_and_every_process() {
+    let output = run(&["-", "--format", "json"], SCENARIO);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["scheduler"], "fcfs");
+    assert_eq!(report["runfor"], 10);
+    assert_eq!(report["log"][0], "  2 processes");
+    assert_eq!(report["processes"][1]["name"], "B");
+    assert_eq!(report["processes"][1]["wait"], 2);
+    assert_eq!(report["processes"][1]["finish_time"], 5);
+
+    let text = stdout(&run(&["-"], SCENARIO));
+    let log: Vec<&str> = report["log"].as_array().unwrap().iter().map(|line| line.as_str().unwrap()).collect();
+    assert_eq!(log, text.lines().collect::<Vec<_>>());
+}
+
+#[test]
+fn csv_has_a_row_per_process() {
+    let output = run(&["-", "--format", "csv"], SCENARIO);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let csv = stdout(&output);
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows.len(), 3, "{}", csv);
+    assert!(rows[0].starts_with("name,arrival,burst,"), "{}", csv);
+    assert!(rows[2].starts_with("B,1,2,5,2,4,2,"), "{}", csv);
+}
+
+#[test]
+fn failures_exit_non_zero_with_an_error() {
+    let cases: [(&[&str], &str, &str); 4] = [
+        (&["does-not-exist.in"], "", "error: could not read does-not-exist.in: "),
+        (&["-"], "runfor 10\nuse nothing\nend\n", "error: could not read -: "),
+        (&["-", "--format", "yaml"], SCENARIO, "invalid value 'yaml'"),
+        (&["-", "--step"], SCENARIO, "error: --step reads its commands from stdin, so the scenario has to come from a file rather than -"),
+    ];
+
+    for (args, stdin, expected) in cases {
+        let output = run(args, stdin);
+        assert!(!output.status.success(), "{:?} succeeded", args);
+        assert!(stderr(&output).contains(expected), "{:?} said {:?}", args, stderr(&output));
+        assert!(stdout(&output).is_empty(), "{:?} wrote {:?}", args, stdout(&output));
+    }
+}
+
+#[test]
+fn without_an_input_there_is_nothing_to_run() {
+    let output = run(&[], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("<INPUT>"), "{}", stderr(&output));
+}
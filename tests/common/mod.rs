@@ -0,0 +1,15 @@
+// helpers shared by the integration tests
+
+use std::fs;
+use std::path::PathBuf;
+
+/// Every sample `.in` scenario at the top of the repository, sorted by name
+pub fn sample_files() -> Vec<PathBuf> {
+    let mut files: Vec<_> = fs::read_dir(".").unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "in"))
+        .collect();
+    files.sort();
+
+    files
+}
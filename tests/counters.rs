@@ -0,0 +1,53 @@
+// pins the selection, preemption and slice counts of known runs
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::process::Process;
+use scheduler_gpt::schedulings::scenario::read_file;
+use scheduler_gpt::schedulings::schedule::{read_contents, ScheduleModel};
+
+fn run(mut model: ScheduleModel) -> Vec<Process> {
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+    model.process_list
+}
+
+/// `(preemptions, selections, slices)` for each process
+fn counts(processes: &[Process]) -> Vec<(&str, i32, i32, i32)> {
+    processes.iter().map(|p| (p.name.as_str(), p.preemptions, p.selections, p.slices)).collect()
+}
+
+#[test]
+fn round_robin_sample() {
+    let (model, _) = read_file("c5-rr.in").unwrap();
+
+    // P1 runs 0-3, loses the cpu to P2 when its quantum expires and comes back for 6-8, so once preempted over two slices
+    // P4 is alone when its quantum expires at 21 and is reselected at once, which keeps the slice it was in
+    assert_eq!(counts(&run(model)), [
+        ("P1", 1, 2, 2),
+        ("P2", 2, 3, 3),
+        ("P3", 0, 1, 1),
+        ("P4", 1, 2, 1),
+        ("P5", 0, 1, 1),
+    ]);
+}
+
+#[test]
+fn reselection_straight_after_a_preemption_keeps_the_slice() {
+    let model = read_contents("runfor 12\nuse rr quantum 2\nprocess name A arrival 0 burst 7\nend".as_bytes()).unwrap();
+
+    // preempted at 2, 4 and 6 with nothing else to run, so a single slice of 7 ticks
+    assert_eq!(counts(&run(model)), [("A", 3, 4, 1)]);
+}
+
+#[test]
+fn a_process_that_runs_to_the_end_is_never_preempted() {
+    let model = read_contents("runfor 12\nuse fcfs\nprocess name A arrival 0 burst 3\nprocess name B arrival 1 burst 2\nend".as_bytes()).unwrap();
+    assert_eq!(counts(&run(model)), [("A", 0, 1, 1), ("B", 0, 1, 1)]);
+}
+
+#[test]
+fn a_preempted_process_that_waits_starts_a_new_slice() {
+    // B arrives shorter at 2 and takes the cpu off A until it finishes at 3
+    let model = read_contents("runfor 12\nuse sjf\nprocess name A arrival 0 burst 5\nprocess name B arrival 2 burst 1\nend".as_bytes()).unwrap();
+    assert_eq!(counts(&run(model)), [("A", 1, 2, 2), ("B", 0, 1, 1)]);
+}
@@ -0,0 +1,68 @@
+// checks the per-process and per-tick CSV exports: their headers, the order of the columns and how awkward names are quoted
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::process::Process;
+use scheduler_gpt::schedulings::report::{write_process_csv, write_timeline_csv};
+use scheduler_gpt::schedulings::scenario::read_file;
+use scheduler_gpt::schedulings::timeline::TickRow;
+
+fn process_csv(processes: &[Process]) -> String {
+    let mut out = vec![];
+    write_process_csv(&mut out, processes).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn timeline_csv(rows: &[TickRow]) -> String {
+    let mut out = vec![];
+    write_timeline_csv(&mut out, rows).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn a_row_per_process_in_column_order() {
+    let (mut model, _) = read_file("c5-rr.in").unwrap();
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+
+    let csv = process_csv(&model.process_list);
+    let mut rows = csv.lines();
+    assert_eq!(rows.next(), Some("name,arrival,burst,finish_time,wait,turnaround,response,preemptions,selections,slices,finished"));
+
+    for process in &model.process_list {
+        let expected = [
+            process.name.clone(), process.arrival_time.to_string(), process.burst_time.to_string(), process.finish_time.to_string(),
+            process.wait_time.to_string(), process.turnaround_time.to_string(), process.response_time.to_string(),
+            process.preemptions.to_string(), process.selections.to_string(), process.slices.to_string(), "true".to_string(),
+        ];
+        assert_eq!(rows.next(), Some(expected.join(",").as_str()));
+    }
+    assert_eq!(rows.next(), None);
+
+    // P1 runs 0-3, loses the cpu to P2 when its quantum expires and finishes its last 2 ticks at 8
+    assert!(csv.contains("\nP1,0,5,8,3,8,0,1,2,2,true\n"), "{}", csv);
+}
+
+#[test]
+fn awkward_names_are_quoted_and_unfinished_runs_left_blank() {
+    let processes = [
+        Process::new("plain".to_string(), 0, 3),
+        Process::new("a,b".to_string(), 1, 2),
+        Process::new("say \"hi\"".to_string(), 2, 1),
+    ];
+
+    assert_eq!(process_csv(&processes), "name,arrival,burst,finish_time,wait,turnaround,response,preemptions,selections,slices,finished\n\
+        plain,0,3,,0,0,0,0,0,0,false\n\
+        \"a,b\",1,2,,0,0,0,0,0,0,false\n\
+        \"say \"\"hi\"\"\",2,1,,0,0,0,0,0,0,false\n");
+}
+
+#[test]
+fn a_row_per_tick_blank_when_idle() {
+    let rows = [
+        TickRow { time: 0, running: Some("A".to_string()), ready: 2 },
+        TickRow { time: 1, running: Some("x,y".to_string()), ready: 0 },
+        TickRow { time: 2, running: None, ready: 0 },
+    ];
+
+    assert_eq!(timeline_csv(&rows), "time,running,ready\n0,A,2\n1,\"x,y\",0\n2,,0\n");
+}
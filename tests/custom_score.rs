@@ -0,0 +1,73 @@
+// parses and scores the expressions `use custom` takes, checking precedence, the functions and what a bad score says
+
+use scheduler_gpt::schedulings::custom::{Expr, Fields};
+use scheduler_gpt::schedulings::process::Process;
+use scheduler_gpt::schedulings::schedule::read_contents;
+
+/// Scores the source for a process that arrived at 4 with a burst of 5, at time 9
+fn score(source: &str) -> f64 {
+    let fields = Fields::of(&Process::new("A".to_string(), 4, 5));
+    Expr::parse(source).unwrap().eval(&fields, 9)
+}
+
+fn error(source: &str) -> String {
+    match Expr::parse(source) {
+        Ok(_) => panic!("{:?} parsed", source),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn operators_follow_the_usual_precedence() {
+    assert_eq!(score("1 + 2 * 3"), 7.0);
+    assert_eq!(score("(1 + 2) * 3"), 9.0);
+    assert_eq!(score("10 - 4 - 3"), 3.0);
+    assert_eq!(score("12 / 3 / 2"), 2.0);
+    assert_eq!(score("-2 * 3"), -6.0);
+    assert_eq!(score("- -2"), 2.0);
+    assert_eq!(score("2 - -3 * 2"), 8.0);
+}
+
+#[test]
+fn names_read_the_process_and_the_time() {
+    assert_eq!(score("burst * 2 + arrival"), 14.0);
+    assert_eq!(score("burst_time - remaining + time_remaining"), 5.0);
+    assert_eq!(score("wait + streak + preemptions"), 0.0);
+    assert_eq!(score("now - arrival_time"), 5.0);
+    assert_eq!(score("time"), 9.0);
+}
+
+#[test]
+fn min_max_and_abs() {
+    assert_eq!(score("min(3, burst)"), 3.0);
+    assert_eq!(score("min(burst, 7, 6)"), 5.0);
+    assert_eq!(score("max(1, 2, burst)"), 5.0);
+    assert_eq!(score("abs(arrival - burst)"), 1.0);
+    assert_eq!(score("max(min(1, 2), abs(-3)) * 2"), 6.0);
+}
+
+#[test]
+fn bad_scores_say_what_is_wrong() {
+    assert_eq!(error(""), "the score ends too early");
+    assert_eq!(error("burst +"), "the score ends too early");
+    assert_eq!(error("1 2"), "unexpected `2` in score");
+    assert_eq!(error("1 $ 2"), "unexpected `$` in score");
+    assert_eq!(error("priority"), "unknown name `priority` in score");
+    assert_eq!(error("(1 + 2"), "expected `)` at the end of the score");
+    assert_eq!(error("min 1"), "expected `(` in score, found `1`");
+    assert_eq!(error("min(1)"), "`min` is given the wrong number of values");
+    assert_eq!(error("abs(1, 2)"), "`abs` is given the wrong number of values");
+}
+
+#[test]
+fn deep_nesting_is_an_error_not_a_stack_overflow() {
+    let nested = |depth: usize| "(".repeat(depth) + "burst" + &")".repeat(depth);
+    assert_eq!(score(&nested(100)), 5.0);
+
+    for source in [nested(100_000), "(".repeat(100_000), "-".repeat(100_000) + "1", "1".to_string() + &"+1".repeat(100_000)] {
+        assert_eq!(error(&source), "a score can have at most 256 numbers, names and symbols");
+
+        let input = format!("runfor 5\nuse custom\nscore = {}\nprocess name A arrival 0 burst 1\nend", source);
+        assert!(read_contents(input.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+// checks the `after` lists of a scenario, that cycles and unknown names are refused and that processes wait for their release
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::process::Process;
+use scheduler_gpt::schedulings::schedule::{read_contents, validate_processes};
+
+fn process(name: &str, after: &[&str]) -> Process {
+    let mut process = Process::new(name.to_string(), 0, 1);
+    process.after = after.iter().map(|name| name.to_string()).collect();
+    process
+}
+
+fn error(processes: &[Process]) -> String {
+    match validate_processes(processes) {
+        Ok(()) => panic!("accepted"),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn cycles_are_named_in_order() {
+    assert_eq!(error(&[process("A", &["A"])]), "processes A -> A wait on each other in a cycle");
+    assert_eq!(error(&[process("A", &["B"]), process("B", &["A"])]), "processes A -> B -> A wait on each other in a cycle");
+
+    // the cycle is found past processes that are not part of it
+    let processes = [
+        process("A", &[]),
+        process("B", &["A", "D"]),
+        process("C", &["B"]),
+        process("D", &["C"]),
+        process("E", &["D"]),
+    ];
+    assert_eq!(error(&processes), "processes B -> D -> C -> B wait on each other in a cycle");
+}
+
+#[test]
+fn shared_dependencies_are_not_cycles() {
+    // a diamond, and a process waiting on the same one from two sides
+    let processes = [
+        process("A", &[]),
+        process("B", &["A"]),
+        process("C", &["A"]),
+        process("D", &["B", "C"]),
+        process("E", &["D", "A", "B"]),
+    ];
+    assert!(validate_processes(&processes).is_ok());
+}
+
+#[test]
+fn unknown_names_are_refused() {
+    assert_eq!(error(&[process("A", &[]), process("B", &["Z"])]), "process B waits on Z, which is not a process");
+}
+
+#[test]
+fn a_held_process_runs_once_released_and_counts_the_held_ticks_as_waiting() {
+    let input = "runfor 20\nuse fcfs\n\
+        process name A arrival 0 burst 3\n\
+        process name B arrival 0 burst 2 after A\n\
+        process name C arrival 1 burst 2 after A B\n\
+        end";
+    let mut model = read_contents(input.as_bytes()).unwrap();
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+
+    let log = cpu.output();
+    for line in ["Time   3 : A finished", "Time   3 : B released", "Time   3 : B selected (burst   2)", "Time   5 : C released"] {
+        assert!(log.iter().any(|l| l == line), "missing `{}`", line);
+    }
+
+    let b = &model.process_list[1];
+    let c = &model.process_list[2];
+    // both run as soon as they are released, so they only waited while held back
+    assert_eq!((b.release_time, b.wait_time, b.turnaround_time, b.response_time), (Some(3), 3, 5, 3));
+    assert_eq!((c.release_time, c.wait_time, c.turnaround_time, c.response_time), (Some(5), 4, 6, 4));
+    assert_eq!(c.turnaround_time, c.finish_time - c.arrival_time);
+}
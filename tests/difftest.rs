@@ -0,0 +1,68 @@
+// holds the engine's log against the naive reference simulators on generated workloads
+
+use scheduler_gpt::schedulings::reference::{difftest, first_divergence, simulate, Reference};
+use scheduler_gpt::schedulings::scenario::read_file;
+use scheduler_gpt::schedulings::workload::{horizon, WorkloadSpec};
+
+const SEEDS: u64 = 200;
+
+#[test]
+fn the_engine_matches_the_reference() {
+    let specs = [
+        WorkloadSpec::default(),
+        // everything arriving at once is all ties
+        WorkloadSpec { max_gap: 0, max_burst: 4, ..WorkloadSpec::default() },
+        // arrivals far apart leave the cpu idle in between
+        WorkloadSpec { max_gap: 10, max_burst: 3, ..WorkloadSpec::default() },
+        WorkloadSpec { processes: 30, max_gap: 1, ..WorkloadSpec::default() },
+    ];
+
+    for reference in Reference::ALL {
+        for (index, spec) in specs.iter().enumerate() {
+            for seed in 0..SEEDS {
+                let processes = spec.generate(seed);
+
+                for time_units in [horizon(&processes), horizon(&processes) / 2] {
+                    if let Some(divergence) = difftest(reference, &processes, time_units).unwrap() {
+                        panic!("{} on workload {} seed {} for {} ticks: {}", reference, index, seed, time_units, divergence);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn the_reference_logs_a_known_run() {
+    let (model, _) = read_file("example.in").unwrap();
+    let log = simulate(Reference::Sjf, &model.process_list, model.time_units).unwrap();
+
+    assert_eq!(log[..8], [
+        "Time   0 : A arrived",
+        "Time   0 : A selected (burst   5)",
+        "Time   1 : B arrived",
+        "Time   4 : C arrived",
+        "Time   5 : A finished",
+        "Time   5 : C selected (burst   2)",
+        "Time   7 : C finished",
+        "Time   7 : B selected (burst   4)",
+    ]);
+    assert_eq!(log[log.len() - 3..], [
+        "A wait   0 turnaround   5 response   0",
+        "B wait   6 turnaround  10 response   6",
+        "C wait   1 turnaround   3 response   1",
+    ]);
+}
+
+#[test]
+fn a_divergence_names_its_tick() {
+    let expected = vec!["Time   0 : A arrived".to_string(), "Time   4 : A selected (burst   2)".to_string()];
+    let actual = vec!["Time   0 : A arrived".to_string(), "Time   4 : Idle".to_string()];
+
+    let divergence = first_divergence(&expected, &actual).unwrap();
+    assert_eq!((divergence.line, divergence.tick), (1, Some(4)));
+    assert!(first_divergence(&expected, &expected).is_none());
+
+    let shorter = first_divergence(&expected, &actual[..1]).unwrap();
+    assert_eq!((shorter.line, shorter.actual), (1, None));
+}
@@ -0,0 +1,70 @@
+// checks when the fairness report flags a process as starved, and that it stays quiet up to the limits
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::fairness::FairnessLimits;
+use scheduler_gpt::schedulings::schedule::read_contents;
+
+const LIMITS: FairnessLimits = FairnessLimits { wait_ratio: 3.0, max_streak: 10 };
+
+fn report(input: &str, limits: &FairnessLimits) -> Vec<String> {
+    let mut model = read_contents(input.as_bytes()).unwrap();
+    CPU::default().run(&mut model);
+    limits.report(&model.process_list)
+}
+
+/// `B` arrives behind `A` under fcfs, so it waits out the whole of `A`'s burst
+fn behind(a_burst: i32, b_burst: i32) -> String {
+    format!("runfor 30\nuse fcfs\nprocess name A arrival 0 burst {}\nprocess name B arrival 0 burst {}\nend", a_burst, b_burst)
+}
+
+#[test]
+fn waiting_exactly_at_the_limits_is_not_starving() {
+    // B waits 3 ticks for a burst of 1, which is not over 3x
+    assert_eq!(report(&behind(3, 1), &LIMITS), [
+        "",
+        "Fairness (flagging a wait over 3x the burst or over 10 ticks ready in a row)",
+        "A longest ready streak   0",
+        "B longest ready streak   3",
+        "0 of 2 processes starved",
+    ]);
+
+    // 10 ticks in a row is not over 10, and 10 is not over 3x a burst of 4
+    let lines = report(&behind(10, 4), &LIMITS);
+    assert_eq!(lines[3], "B longest ready streak  10");
+    assert_eq!(lines[4], "0 of 2 processes starved");
+}
+
+#[test]
+fn a_long_wait_for_a_short_burst_is_flagged() {
+    let lines = report(&behind(4, 1), &LIMITS);
+    assert_eq!(lines[3], "B longest ready streak   4 starved: waited 4 ticks for a burst of 1");
+    assert_eq!(lines[4], "1 of 2 processes starved");
+}
+
+#[test]
+fn a_long_ready_streak_is_flagged_whatever_the_burst() {
+    // B's wait of 11 is well under 3x its burst of 20, but it was ready for all of it
+    let lines = report(&behind(11, 20), &LIMITS);
+    assert_eq!(lines[3], "B longest ready streak  11 starved: ready 11 ticks in a row");
+    assert_eq!(lines[4], "1 of 2 processes starved");
+}
+
+#[test]
+fn both_reasons_are_given_together() {
+    let lines = report(&behind(11, 1), &LIMITS);
+    assert_eq!(lines[3], "B longest ready streak  11 starved: waited 11 ticks for a burst of 1, ready 11 ticks in a row");
+}
+
+#[test]
+fn a_streak_only_counts_ticks_in_a_row() {
+    // rr hands the cpu back and forth, so B waits 10 ticks in all but never more than 2 in a row
+    let input = "runfor 30\nuse rr quantum 2\nprocess name A arrival 0 burst 10\nprocess name B arrival 0 burst 10\nend";
+    let strict = FairnessLimits { wait_ratio: 3.0, max_streak: 2 };
+    let lines = report(input, &strict);
+    assert_eq!(lines[2], "A longest ready streak   2");
+    assert_eq!(lines[3], "B longest ready streak   2");
+    assert_eq!(lines[4], "0 of 2 processes starved");
+
+    let stricter = FairnessLimits { max_streak: 1, ..strict };
+    assert_eq!(report(input, &stricter).last().unwrap(), "2 of 2 processes starved");
+}
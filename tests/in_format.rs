@@ -0,0 +1,77 @@
+// reads .in scenarios written with comments, blank lines and fields in any order, and checks what a bad line says
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::schedule::{read_contents, ScheduleModel};
+
+fn read(input: &str) -> ScheduleModel {
+    read_contents(input.as_bytes()).unwrap_or_else(|e| panic!("{:?} did not parse: {}", input, e))
+}
+
+fn error(input: &str) -> String {
+    match read_contents(input.as_bytes()) {
+        Ok(_) => panic!("{:?} parsed", input),
+        Err(e) => e.to_string(),
+    }
+}
+
+fn log(mut model: ScheduleModel) -> Vec<String> {
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+    cpu.output().to_vec()
+}
+
+#[test]
+fn reordered_and_commented_files_run_the_same() {
+    let plain = "processcount 3\nrunfor 20\nuse rr\nquantum 3\n\
+        process name P1 arrival 0 burst 5\nprocess name P2 arrival 2 burst 9\nprocess name P3 arrival 9 burst 3\nend\n";
+    let shuffled = "# the same run as plain\n\
+        \n\
+        quantum 3   # before the use line\n\
+        runfor 20\n\
+        \t\n\
+        use rr\n\
+        process burst 5 name P1 arrival 0\n\
+        process arrival 2 burst 9 name P2 # trailing comment\n\
+        # process name P9 arrival 0 burst 1\n\
+        process name P3 burst 3 arrival 9\n\
+        end\n\
+        anything after end is ignored\n";
+    let inline = "runfor 20\nuse rr quantum 3\n\
+        process name P1 arrival 0 burst 5\nprocess name P2 arrival 2 burst 9\nprocess name P3 arrival 9 burst 3\nend";
+
+    let expected = log(read(plain));
+    assert_eq!(log(read(shuffled)), expected);
+    assert_eq!(log(read(inline)), expected);
+}
+
+#[test]
+fn processcount_defaults_to_the_process_lines() {
+    let model = read("runfor 5\nuse fcfs\nprocess name A arrival 0 burst 1\nprocess name B arrival 1 burst 1\nend");
+    assert_eq!(model.number_of_processes, 2);
+
+    let model = read("processcount 7\nrunfor 5\nuse fcfs\nprocess name A arrival 0 burst 1\nend");
+    assert_eq!(model.number_of_processes, 7);
+}
+
+#[test]
+fn bad_lines_name_their_line() {
+    let cases = [
+        ("runfor\nuse fcfs\nend", "line 1: `runfor` needs exactly one value"),
+        ("runfor 5\nrunfor 6\nuse fcfs\nend", "line 2: `runfor` is given more than once"),
+        ("runfor five\nuse fcfs\nend", "line 1: `runfor` must be a whole number, found `five`"),
+        ("# header\n\nrunfor 5\nuse\nend", "line 4: `use` needs a scheduler name"),
+        ("runfor 5\nuse rr\nquantum 3\nquantum 4\nend", "line 4: `quantum` is given more than once"),
+        ("runfor 5\nuse fcfs\nprocess name A arrival\nend", "line 3: `arrival` is missing a value"),
+        ("runfor 5\nuse fcfs\nprocess name A arrival 0 burst 1 colour red\nend", "line 3: unknown process field `colour`"),
+        ("runfor 5\nuse fcfs\nprocess name A arrival 0 burst x\nend", "line 3: `burst` must be a whole number, found `x`"),
+        ("runfor 5\nuse fcfs\nprocess name A name B arrival 0 burst 1\nend", "line 3: `name` is given more than once"),
+        ("runfor 5\nuse fcfs\nprocess arrival 0 burst 1\nend", "line 3: process is missing a `name`"),
+        ("runfor 5\nuse fcfs\nprocess name A arrival 0 burst 1\n", "no end stated"),
+        ("runfor 5\nprocess name A arrival 0 burst 1\nend", "no `use` line found"),
+        ("use fcfs\nprocess name A arrival 0 burst 1\nend", "no `runfor` line found"),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(error(input), expected, "{:?}", input);
+    }
+}
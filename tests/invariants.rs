@@ -0,0 +1,137 @@
+// runs generated workloads under every scheduler and checks the relations `Process::tick` keeps by hand
+
+use serde_json::Value;
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::process::{Process, ProcessState};
+use scheduler_gpt::schedulings::schedule::ScheduleModel;
+use scheduler_gpt::schedulings::workload::{build, horizon, model, schedulers, WorkloadSpec};
+
+const SEEDS: u64 = 100;
+
+/// The workloads every scheduler is run over
+fn specs() -> Vec<WorkloadSpec> {
+    let plain = WorkloadSpec::default();
+
+    vec![
+        plain.clone(),
+        WorkloadSpec { max_gap: 0, ..plain.clone() },
+        WorkloadSpec { max_gap: 12, max_burst: 3, ..plain.clone() },
+        WorkloadSpec { max_bursts: 3, ..plain.clone() },
+        WorkloadSpec { processes: 15, dependency_percent: 40, ..plain.clone() },
+        WorkloadSpec { processes: 15, max_bursts: 2, dependency_percent: 30, ..plain },
+    ]
+}
+
+/// What was seen tick by tick while the model ran
+#[derive(Default)]
+struct Watched {
+    /// Ticks that started with a process on the cpu
+    busy: i32,
+}
+
+/// Checks everything that has to hold after each tick, counting the ticks the cpu is handed to a process
+fn check_tick(model: &ScheduleModel, t: i32, watched: &mut Watched, context: &str) {
+    let running: Vec<&Process> = model.process_list.iter().filter(|p| p.state == Some(ProcessState::Running)).collect();
+    assert!(running.len() <= 1, "{}: {} processes running at {}", context, running.len(), t);
+
+    // a process has been in the system since it arrived, ticks spent held back on its dependencies included
+    for process in model.process_list.iter().filter(|p| !p.finished() && p.released_by(t)) {
+        assert_eq!(process.turnaround_time, t - process.arrival_time, "{}: {} turnaround at {} is not the time since it arrived", context, process.name, t);
+    }
+
+    if let Some(process) = running.first() {
+        assert!(process.released_by(t), "{}: {} runs at {} before it arrives or is released", context, process.name, t);
+        assert_eq!(model.scheduler.selected_process_name().as_ref(), Some(&process.name), "{}: the running process is not the selected one at {}", context, t);
+
+        // the last selection never gets to run
+        if t < model.time_units - 1 {
+            watched.busy += 1;
+        }
+    }
+    else {
+        // none of the schedulers leaves the cpu idle with something ready to run
+        let waiting = model.process_list.iter().find(|p| !p.finished() && p.released_by(t));
+        assert!(waiting.is_none(), "{}: idle at {} with {} ready", context, t, waiting.unwrap().name);
+    }
+}
+
+/// Checks every process once the run is over
+fn check_end(model: &ScheduleModel, watched: &Watched, context: &str) {
+    let mut work_done = 0;
+
+    for process in &model.process_list {
+        let ran = process.burst_time - process.time_remaining;
+        work_done += ran;
+
+        assert!((0..=process.burst_time).contains(&process.time_remaining), "{}: {} has {} left", context, process.name, process.time_remaining);
+        assert_eq!(process.turnaround_time, process.wait_time + ran, "{}: {} turnaround is not its wait plus the time it ran", context, process.name);
+        assert!(process.response_time <= process.wait_time, "{}: {} responded after more than its wait", context, process.name);
+
+        if process.finished() {
+            assert_eq!(process.finish_time - process.arrival_time, process.turnaround_time, "{}: {} finish time is off its turnaround", context, process.name);
+            assert_eq!(process.turnaround_time, process.wait_time + process.burst_time, "{}: {}", context, process.name);
+        }
+
+        // the ticks held back count as waiting, and nothing can respond before its release
+        if let Some(release_time) = process.release_time {
+            let held = release_time - process.arrival_time;
+            assert!(process.wait_time >= held && process.response_time >= held, "{}: {} does not count the {} ticks it was held as waiting", context, process.name, held);
+        }
+    }
+
+    assert_eq!(work_done, watched.busy, "{}: the work done does not match the ticks the cpu was busy", context);
+}
+
+fn run(processes: Vec<Process>, keyword: &str, params: &Value, time_units: i32, context: &str) -> ScheduleModel {
+    let mut model = model(processes, build(keyword, params).unwrap(), time_units).unwrap();
+    let mut watched = Watched::default();
+
+    CPU::default().run_with(&mut model, |_, model, t| check_tick(model, t, &mut watched, context));
+    check_end(&model, &watched, context);
+
+    model
+}
+
+#[test]
+fn every_process_finishes_with_consistent_times() {
+    for (keyword, params) in schedulers() {
+        for (index, spec) in specs().iter().enumerate() {
+            for seed in 0..SEEDS {
+                let processes = spec.for_scheduler(keyword).generate(seed);
+                let context = format!("{} {} on workload {} seed {}", keyword, params, index, seed);
+                let time_units = horizon(&processes);
+
+                let model = run(processes, keyword, &params, time_units, &context);
+                let unfinished = model.process_list.iter().find(|p| !p.finished());
+                assert!(unfinished.is_none(), "{}: {} did not finish", context, unfinished.unwrap().name);
+            }
+        }
+    }
+}
+
+#[test]
+fn cutting_a_run_short_keeps_the_counts_consistent() {
+    for (keyword, params) in schedulers() {
+        for (index, spec) in specs().iter().enumerate() {
+            for seed in 0..SEEDS / 4 {
+                let processes = spec.for_scheduler(keyword).generate(seed);
+                let context = format!("{} {} on workload {} seed {} cut short", keyword, params, index, seed);
+                let time_units = horizon(&processes) / 3;
+
+                run(processes, keyword, &params, time_units, &context);
+            }
+        }
+    }
+}
+
+#[test]
+fn the_same_seed_gives_the_same_workload() {
+    let spec = WorkloadSpec { max_bursts: 3, dependency_percent: 50, ..WorkloadSpec::default() };
+    let describe = |processes: Vec<Process>| -> Vec<(String, i32, Vec<i32>, Vec<String>)> {
+        processes.into_iter().map(|p| (p.name, p.arrival_time, p.bursts, p.after)).collect()
+    };
+
+    assert_eq!(describe(spec.generate(7)), describe(spec.generate(7)));
+    assert_ne!(describe(spec.generate(7)), describe(spec.generate(8)));
+}
@@ -0,0 +1,87 @@
+// runs processes that do I/O between their cpu bursts, checking they leave the ready queue for it and what it adds to their times
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::process::Process;
+use scheduler_gpt::schedulings::scenario::Scenario;
+use scheduler_gpt::schedulings::schedule::{read_contents, ScheduleModel};
+
+fn run(mut model: ScheduleModel) -> (Vec<String>, Vec<Process>) {
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+    (cpu.output().to_vec(), model.process_list)
+}
+
+fn events(log: &[String]) -> Vec<&str> {
+    log.iter().map(String::as_str).filter(|line| line.starts_with("Time") && !line.ends_with("Idle")).collect()
+}
+
+#[test]
+fn another_process_runs_while_one_does_io() {
+    let (log, processes) = run(read_contents("runfor 10\nuse fcfs\n\
+        process name A arrival 0 bursts 2,2 io 3\n\
+        process name B arrival 0 burst 4\n\
+        end".as_bytes()).unwrap());
+
+    assert_eq!(events(&log), [
+        "Time   0 : A arrived",
+        "Time   0 : B arrived",
+        "Time   0 : A selected (burst   2)",
+        "Time   2 : A finished burst 1 of 2",
+        "Time   2 : B selected (burst   4)",
+        "Time   5 : A finished I/O",
+        "Time   6 : B finished",
+        "Time   6 : A selected (burst   2)",
+        "Time   8 : A finished",
+    ]);
+
+    // A only waits from the end of its I/O at 5 until B finishes at 6, and its turnaround covers the I/O
+    let a = &processes[0];
+    assert_eq!((a.wait_time, a.turnaround_time, a.io_time()), (1, 8, 3));
+    assert_eq!(a.turnaround_time, a.wait_time + a.burst_time + a.io_time());
+}
+
+#[test]
+fn the_cpu_idles_while_the_only_process_does_io() {
+    let (log, processes) = run(read_contents("runfor 6\nuse rr quantum 2\nprocess name A arrival 0 bursts 1,1 io 2\nend".as_bytes()).unwrap());
+
+    for line in ["Time   1 : A finished burst 1 of 2", "Time   1 : Idle", "Time   2 : Idle", "Time   3 : A finished I/O", "Time   3 : A selected (burst   1)"] {
+        assert!(log.iter().any(|l| l == line), "missing `{}` in {:#?}", line, log);
+    }
+    // coming back from I/O is not a preemption, and the burst after it is a new slice
+    let a = &processes[0];
+    assert_eq!((a.preemptions, a.selections, a.slices, a.wait_time, a.finish_time), (0, 2, 2, 0, 4));
+}
+
+#[test]
+fn no_io_goes_straight_back_to_the_ready_queue() {
+    let with = "runfor 10\nuse sjf\nprocess name A arrival 0 bursts 2,3 io 0\nprocess name B arrival 1 burst 2\nend";
+    let without = "runfor 10\nuse sjf\nprocess name A arrival 0 bursts 2,3\nprocess name B arrival 1 burst 2\nend";
+
+    let (log, _) = run(read_contents(with.as_bytes()).unwrap());
+    assert_eq!(log, run(read_contents(without.as_bytes()).unwrap()).0);
+    assert!(!log.iter().any(|line| line.contains("I/O")));
+}
+
+#[test]
+fn structured_scenarios_take_io_too() {
+    let toml = "runfor = 10\n[scheduler]\nuse = \"fcfs\"\n\
+        [[processes]]\nname = \"A\"\narrival = 0\nbursts = [2, 2]\nio = 3\n\
+        [[processes]]\nname = \"B\"\narrival = 0\nburst = 4\n";
+    let plain = "runfor 10\nuse fcfs\nprocess name A arrival 0 bursts 2,2 io 3\nprocess name B arrival 0 burst 4\nend";
+
+    let (log, _) = run(Scenario::from_toml(toml).unwrap().into_model().unwrap());
+    assert_eq!(log, run(read_contents(plain.as_bytes()).unwrap()).0);
+}
+
+#[test]
+fn io_has_to_be_a_whole_number_of_at_least_0() {
+    for (input, expected) in [
+        ("runfor 5\nuse fcfs\nprocess name A arrival 0 bursts 1,1 io -1\nend", "process A cannot do less than 0 ticks of I/O"),
+        ("runfor 5\nuse fcfs\nprocess name A arrival 0 bursts 1,1 io soon\nend", "line 3: `io` must be a whole number, found `soon`"),
+    ] {
+        match read_contents(input.as_bytes()) {
+            Ok(_) => panic!("{:?} parsed", input),
+            Err(e) => assert_eq!(e.to_string(), expected),
+        }
+    }
+}
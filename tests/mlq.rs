@@ -0,0 +1,110 @@
+// checks how mlq shares the cpu between its classes under the strict and slice policies
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::process::Process;
+use scheduler_gpt::schedulings::scenario::read_file;
+use scheduler_gpt::schedulings::schedule::{read_contents, ScheduleModel};
+
+fn run(mut model: ScheduleModel) -> (Vec<String>, Vec<Process>) {
+    let mut cpu = CPU { verbose: true, ..CPU::default() };
+    cpu.run(&mut model);
+    (cpu.output().to_vec(), model.process_list)
+}
+
+/// The lines that hand the cpu over or take it away, without the arrivals and idle ticks
+fn handovers(log: &[String]) -> Vec<&str> {
+    log.iter()
+        .map(String::as_str)
+        .filter(|line| line.starts_with("Time") && !line.ends_with("arrived") && !line.ends_with("Idle"))
+        .collect()
+}
+
+fn selections(processes: &[Process]) -> Vec<(&str, i32)> {
+    processes.iter().map(|p| (p.name.as_str(), p.selections)).collect()
+}
+
+#[test]
+fn strict_priority_runs_a_class_only_while_the_ones_above_are_empty() {
+    let (model, _) = read_file("c5-mlq.in").unwrap();
+    let (log, processes) = run(model);
+
+    assert_eq!(handovers(&log), [
+        "Time   0 : B1 selected (burst   6)",
+        // interactive is above batch, so I1 takes the cpu the tick it arrives
+        "Time   1 : B1 preempted by I1 (remaining 5)",
+        "Time   1 : I1 selected (burst   5)",
+        "Time   3 : I1 quantum expired",
+        "Time   3 : I2 selected (burst   3)",
+        // system is above both, and I2 keeps the tick left of its quantum while it waits
+        "Time   4 : I2 preempted by S1 (remaining 2)",
+        "Time   4 : S1 selected (burst   2)",
+        "Time   6 : S1 finished",
+        "Time   6 : I2 selected (burst   2)",
+        "Time   7 : I2 quantum expired",
+        "Time   7 : I1 selected (burst   3)",
+        "Time   9 : I1 quantum expired",
+        "Time   9 : I2 selected (burst   1)",
+        "Time  10 : I2 finished",
+        "Time  10 : I1 selected (burst   1)",
+        "Time  11 : I1 finished",
+        // batch's fcfs still has B1 picked from before it lost the cpu, so B2 goes last
+        "Time  11 : B1 selected (burst   5)",
+        "Time  16 : B1 finished",
+        "Time  16 : B2 selected (burst   2)",
+        "Time  18 : B2 finished",
+    ]);
+
+    // arriving in a class that does not hold the cpu selects nothing
+    assert_eq!(selections(&processes), [("B1", 2), ("I1", 3), ("I2", 3), ("B2", 1), ("S1", 1)]);
+}
+
+#[test]
+fn slices_take_turns_whatever_the_priority() {
+    let input = "runfor 20\nuse mlq policy slice\n\
+        class interactive rr quantum 2 slice 3\n\
+        class batch fcfs slice 2\n\
+        process name B1 arrival 0 burst 4 class batch\n\
+        process name I1 arrival 0 burst 5 class interactive\n\
+        process name I2 arrival 1 burst 2 class interactive\n\
+        end";
+    let (log, processes) = run(read_contents(input.as_bytes()).unwrap());
+
+    assert_eq!(handovers(&log), [
+        "Time   0 : I1 selected (burst   5)",
+        "Time   2 : I1 quantum expired",
+        "Time   2 : I2 selected (burst   2)",
+        // interactive has had its 3 ticks, so batch gets its 2 even with interactive still waiting
+        "Time   3 : I2 preempted by B1 (remaining 1)",
+        "Time   3 : B1 selected (burst   4)",
+        "Time   5 : B1 preempted by I2 (remaining 2)",
+        "Time   5 : I2 selected (burst   1)",
+        "Time   6 : I2 finished",
+        "Time   6 : I1 selected (burst   3)",
+        "Time   8 : I1 quantum expired",
+        "Time   8 : B1 selected (burst   2)",
+        "Time  10 : B1 finished",
+        // with batch empty the turn comes straight back round
+        "Time  10 : I1 selected (burst   1)",
+        "Time  11 : I1 finished",
+    ]);
+    assert_eq!(selections(&processes), [("B1", 2), ("I1", 3), ("I2", 2)]);
+}
+
+#[test]
+fn a_class_keeps_the_cpu_past_its_slice_when_no_other_has_work() {
+    let input = "runfor 20\nuse mlq policy slice\n\
+        class interactive rr quantum 5 slice 2\n\
+        class batch fcfs slice 2\n\
+        process name I1 arrival 0 burst 4 class interactive\n\
+        process name B1 arrival 6 burst 1 class batch\n\
+        end";
+    let (log, processes) = run(read_contents(input.as_bytes()).unwrap());
+
+    assert_eq!(handovers(&log), [
+        "Time   0 : I1 selected (burst   4)",
+        "Time   4 : I1 finished",
+        "Time   6 : B1 selected (burst   1)",
+        "Time   7 : B1 finished",
+    ]);
+    assert_eq!(selections(&processes), [("I1", 1), ("B1", 1)]);
+}
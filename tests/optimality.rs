@@ -0,0 +1,89 @@
+// holds sjf and realSJF against the best schedules the optimality check finds
+
+use scheduler_gpt::schedulings::optimal::{best_non_preemptive, best_preemptive, check, Check};
+use scheduler_gpt::schedulings::scenario::read_file;
+use scheduler_gpt::schedulings::schedule::{read_contents, ScheduleModel};
+
+/// Small workloads with ties, gaps between arrivals and late short jobs, each as `(arrival, burst)`
+const WORKLOADS: [&[(i32, i32)]; 8] = [
+    &[(0, 5), (2, 9), (9, 3), (7, 4), (11, 1)],
+    &[(0, 3), (0, 3), (0, 3)],
+    &[(0, 8), (1, 4), (2, 2), (3, 1)],
+    &[(3, 2), (3, 1), (10, 4), (10, 4), (12, 1)],
+    &[(0, 10), (1, 1), (1, 1), (2, 1), (20, 2)],
+    &[(5, 1)],
+    &[(0, 4), (4, 4), (8, 4), (1, 3), (2, 6), (3, 1)],
+    &[(0, 1), (0, 7), (1, 2), (1, 6), (2, 3), (2, 5), (3, 4)],
+];
+
+fn model(workload: &[(i32, i32)]) -> ScheduleModel {
+    let mut input = format!("processcount {}\nrunfor 100\nuse fcfs\n", workload.len());
+    for (index, (arrival, burst)) in workload.iter().enumerate() {
+        input += &format!("process name P{} arrival {} burst {}\n", index + 1, arrival, burst);
+    }
+    input += "end\n";
+
+    read_contents(input.as_bytes()).unwrap()
+}
+
+fn checks(model: &ScheduleModel) -> (Check, Check) {
+    let mut checks = check(model).unwrap().into_iter();
+    (checks.next().unwrap(), checks.next().unwrap())
+}
+
+#[test]
+fn sjf_matches_the_best_preemptive_schedule() {
+    for workload in WORKLOADS {
+        let (_, preemptive) = checks(&model(workload));
+        assert_eq!(preemptive.gap(), Some(0.0), "sjf on {:?}", workload);
+    }
+}
+
+#[test]
+fn real_sjf_never_beats_the_best_non_preemptive_schedule() {
+    for workload in WORKLOADS {
+        let (non_preemptive, preemptive) = checks(&model(workload));
+
+        assert!(non_preemptive.gap().unwrap() >= 0.0, "realSJF on {:?}", workload);
+        // preempting can only help
+        assert!(preemptive.best.average_wait <= non_preemptive.best.average_wait, "{:?}", workload);
+    }
+}
+
+#[test]
+fn real_sjf_is_best_when_everything_arrives_at_once() {
+    let (non_preemptive, _) = checks(&model(&[(0, 6), (0, 2), (0, 9), (0, 1), (0, 4)]));
+    assert_eq!(non_preemptive.gap(), Some(0.0));
+    assert_eq!(non_preemptive.best.order, ["P4", "P2", "P5", "P1", "P3"]);
+}
+
+#[test]
+fn waiting_for_a_short_job_can_beat_realsjf() {
+    // realSJF starts P2 as soon as P1 is done, but idling two ticks for P4 and P3 waits less in all
+    let processes = model(WORKLOADS[0]).process_list;
+    let best = best_non_preemptive(&processes);
+
+    assert_eq!(best.average_wait, 3.2);
+    assert_eq!(best.order, ["P1", "P4", "P5", "P3", "P2"]);
+    assert_eq!(best_preemptive(&processes).average_wait, 2.8);
+}
+
+#[test]
+fn sample_scenarios() {
+    for file in ["c2-fcfs.in", "c5-sjf.in", "c5_realSJF.in", "c5-rr.in", "c10-sjf.in"] {
+        let (model, _) = read_file(file).unwrap();
+        let (non_preemptive, preemptive) = checks(&model);
+
+        assert!(non_preemptive.gap().unwrap() >= 0.0, "realSJF on {}", file);
+        assert_eq!(preemptive.gap(), Some(0.0), "sjf on {}", file);
+    }
+}
+
+#[test]
+fn rejects_what_it_cannot_check() {
+    let (dependencies, _) = read_file("c5-dag.in").unwrap();
+    assert!(check(&dependencies).is_err());
+
+    let too_many: Vec<(i32, i32)> = (0..11).map(|i| (i, 2)).collect();
+    assert!(check(&model(&too_many)).is_err());
+}
@@ -0,0 +1,111 @@
+// pins the logs of scenarios whose output changed when a scheduler's selection was fixed, against the files in tests/outputs
+
+use std::fs;
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::scenario::read_file;
+use scheduler_gpt::schedulings::schedule::read_contents;
+
+/// The log a scenario file writes, the way it lands in its `.out` file
+fn output(file: &str) -> String {
+    let (mut model, _) = read_file(file).unwrap();
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+
+    let mut out = vec![];
+    cpu.write_output(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn expected(name: &str) -> String {
+    fs::read_to_string(format!("tests/outputs/{}", name)).unwrap()
+}
+
+fn log(input: &str) -> Vec<String> {
+    let mut model = read_contents(input.as_bytes()).unwrap();
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+    cpu.output().to_vec()
+}
+
+#[test]
+fn real_sjf_sample_is_unchanged() {
+    assert_eq!(output("c5_realSJF.in"), expected("c5_realSJF.out"));
+}
+
+#[test]
+fn real_sjf_picks_once_every_arrival_of_the_tick_is_in() {
+    // A used to get the idle cpu for being read first, though B is shorter and arrives in the same tick
+    let log = log("runfor 10\nuse realSJF\n\
+        process name A arrival 0 burst 5\n\
+        process name B arrival 0 burst 2\n\
+        end");
+
+    assert_eq!(log[4], "Time   0 : B selected (burst   2)");
+    assert!(!log.iter().any(|line| line == "Time   0 : A selected (burst   5)"));
+}
+
+#[test]
+fn real_sjf_breaks_ties_by_name() {
+    // C and B are equally short when A finishes, and used to be picked in hash map order
+    let log = log("runfor 10\nuse realSJF\n\
+        process name A arrival 0 burst 2\n\
+        process name C arrival 1 burst 3\n\
+        process name B arrival 1 burst 3\n\
+        end");
+
+    for line in ["Time   2 : B selected (burst   3)", "Time   5 : C selected (burst   3)"] {
+        assert!(log.iter().any(|l| l == line), "missing `{}` in {:#?}", line, log);
+    }
+}
+
+#[test]
+fn sjf_example_only_changed_where_a_tie_used_to_preempt() {
+    let before = expected("example.before.out");
+    let after = expected("example.out");
+    assert_eq!(output("example.in"), after);
+
+    // B's burst of 4 ties with what A has left at time 1, which used to hand B the cpu
+    let dropped: Vec<&str> = before.lines().filter(|line| !after.lines().any(|l| l == *line)).collect();
+    let added: Vec<&str> = after.lines().filter(|line| !before.lines().any(|l| l == *line)).collect();
+    assert_eq!(dropped, [
+        "Time   1 : B selected (burst   4)",
+        "Time   5 : B finished",
+        "Time   7 : A selected (burst   4)",
+        "Time  11 : A finished",
+        "A wait   6 turnaround  11 response   0",
+        "B wait   0 turnaround   4 response   0",
+    ]);
+    assert_eq!(added, [
+        "Time   5 : A finished",
+        "Time   7 : B selected (burst   4)",
+        "Time  11 : B finished",
+        "A wait   0 turnaround   5 response   0",
+        "B wait   6 turnaround  10 response   6",
+    ]);
+}
+
+#[test]
+fn sjf_selects_once_for_arrivals_on_an_idle_cpu() {
+    let log = log("runfor 10\nuse sjf\n\
+        process name A arrival 0 burst 5\n\
+        process name B arrival 0 burst 2\n\
+        end");
+
+    let selected: Vec<&String> = log.iter().filter(|line| line.starts_with("Time   0") && line.contains("selected")).collect();
+    assert_eq!(selected, ["Time   0 : B selected (burst   2)"]);
+}
+
+#[test]
+fn fcfs_breaks_ties_by_name() {
+    // C and B join the queue in the same tick, and used to be picked in hash map order
+    let log = log("runfor 10\nuse fcfs\n\
+        process name A arrival 0 burst 2\n\
+        process name C arrival 1 burst 1\n\
+        process name B arrival 1 burst 1\n\
+        end");
+
+    for line in ["Time   2 : B selected (burst   1)", "Time   3 : C selected (burst   1)"] {
+        assert!(log.iter().any(|l| l == line), "missing `{}` in {:#?}", line, log);
+    }
+}
@@ -0,0 +1,29 @@
+// maps items across threads and checks the results come back in the order of the items, whatever the job count
+
+use std::thread;
+use std::time::Duration;
+
+use scheduler_gpt::schedulings::parallel::map_parallel;
+
+#[test]
+fn results_keep_the_order_of_the_items() {
+    let items: Vec<u64> = (0..12).collect();
+    let expected: Vec<u64> = items.iter().map(|item| item * 10).collect();
+
+    // more jobs than items included, and 0 which runs them one at a time
+    for jobs in [0, 1, 2, 3, 5, 12, 40] {
+        // the first items take longest, so they finish last when they run side by side
+        let results = map_parallel(jobs, items.clone(), |item| {
+            thread::sleep(Duration::from_millis(12 - item));
+            item * 10
+        });
+        assert_eq!(results, expected, "{} jobs", jobs);
+    }
+}
+
+#[test]
+fn nothing_in_is_nothing_out() {
+    for jobs in [1, 4] {
+        assert!(map_parallel(jobs, Vec::<u64>::new(), |item| item).is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+// throws mangled scenario files at the parser, which has to turn every one into a model or an error without panicking
+
+mod common;
+
+use std::fs;
+
+use common::sample_files;
+use scheduler_gpt::schedulings::process::Process;
+use scheduler_gpt::schedulings::schedule::read_contents;
+use scheduler_gpt::schedulings::workload::Rng;
+
+const ROUNDS: u64 = 3000;
+
+/// Words the parser knows, so the random lines get past the first keyword now and then
+const WORDS: [&str; 40] = [
+    "processcount", "runfor", "use", "process", "end", "class", "name", "arrival", "burst", "bursts", "after",
+    "quantum", "aging", "alpha", "tau0", "policy", "slice", "score", "=", "preemptive", "fcfs", "sjf", "realSJF",
+    "sjf-predict", "rr", "mlq", "custom", "strict", "0", "1", "-1", "3", "2147483647", "-2147483648", "1,2",
+    "((", ")", "min(", "remaining", "#",
+];
+
+fn samples() -> Vec<Vec<u8>> {
+    sample_files().into_iter().map(|path| fs::read(path).unwrap()).collect()
+}
+
+/// Flips, drops, duplicates and inserts bytes of a valid file
+fn mangle(rng: &mut Rng, input: &[u8]) -> Vec<u8> {
+    let mut bytes = input.to_vec();
+
+    for _ in 0..rng.between(1, 8) {
+        let at = rng.below(bytes.len() as u64 + 1) as usize;
+        match rng.below(5) {
+            0 if at < bytes.len() => bytes[at] = rng.next_u64() as u8,
+            1 if at < bytes.len() => { bytes.remove(at); },
+            2 => bytes.insert(at, b"\n #=,-0123456789()"[rng.below(18) as usize]),
+            3 if at < bytes.len() => {
+                let end = (at + rng.below(40) as usize).min(bytes.len());
+                let copy = bytes[at..end].to_vec();
+                bytes.splice(at..at, copy);
+            },
+            _ => {
+                let word = WORDS[rng.below(WORDS.len() as u64) as usize];
+                bytes.splice(at..at, format!(" {} ", word).into_bytes());
+            },
+        }
+    }
+
+    bytes
+}
+
+/// Lines built out of the known words
+fn word_soup(rng: &mut Rng) -> String {
+    (0..rng.between(0, 12))
+        .map(|_| {
+            (0..rng.between(0, 8))
+                .map(|_| WORDS[rng.below(WORDS.len() as u64) as usize])
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn mangled_samples_never_panic() {
+    let samples = samples();
+    let mut rng = Rng::new(1);
+
+    for _ in 0..ROUNDS {
+        let sample = &samples[rng.below(samples.len() as u64) as usize];
+        let _ = read_contents(mangle(&mut rng, sample).as_slice());
+    }
+}
+
+#[test]
+fn word_soup_never_panics() {
+    let mut rng = Rng::new(2);
+
+    for _ in 0..ROUNDS {
+        let soup = word_soup(&mut rng);
+        let _ = read_contents(soup.as_bytes());
+
+        let tokens: Vec<&str> = soup.split_whitespace().collect();
+        let _ = Process::parse(&tokens);
+    }
+}
+
+#[test]
+fn inputs_that_used_to_break_the_parser() {
+    let inputs = [
+        "",
+        "\n\n\n",
+        "end",
+        "process\nend",
+        "use\nend",
+        "use nothing\nruntime 3\nend",
+        "processcount 1\nrunfor 5\nuse fcfs\nprocess name A arrival 0 bursts 2147483647,1\nend",
+        "processcount 1\nrunfor 5\nuse fcfs\nprocess name A arrival 0 burst -5\nend",
+        "processcount 1\nrunfor 5\nuse rr quantum 99999999999\nprocess name A arrival 0 burst 1\nend",
+    ];
+
+    for input in inputs {
+        assert!(read_contents(input.as_bytes()).is_err(), "{:?}", input);
+    }
+}
+
+#[test]
+fn long_dependency_chains_do_not_overflow_the_stack() {
+    let mut input = "runfor 5\nuse fcfs\nprocess name P0 arrival 0 burst 1\n".to_string();
+    for index in 1..100_000 {
+        input += &format!("process name P{} arrival 0 burst 1 after P{}\n", index, index - 1);
+    }
+    input += "end\n";
+
+    assert!(read_contents(input.as_bytes()).is_ok());
+}
@@ -0,0 +1,60 @@
+// checks sjf-predict's exponential averages, the jobs they pick and the prediction error it ends the log with
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::schedule::read_contents;
+
+fn log(input: &str) -> Vec<String> {
+    let mut model = read_contents(input.as_bytes()).unwrap();
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+    cpu.output().to_vec()
+}
+
+/// The lines after the `Burst prediction error` heading
+fn summary(log: &[String]) -> Vec<&str> {
+    let heading = log.iter().position(|line| line == "Burst prediction error").expect("no prediction summary");
+    log[heading + 1..].iter().map(String::as_str).collect()
+}
+
+#[test]
+fn predictions_are_exponential_averages_of_the_bursts_run() {
+    // 10, then 0.5 * 4 + 0.5 * 10 = 7, then 0.5 * 8 + 0.5 * 7 = 7.5, off by 6, 1 and 5.5
+    let averaged = log("runfor 20\nuse sjf-predict alpha 0.5 tau0 10\nprocess name A arrival 0 bursts 4,8,2 io 1\nend");
+    assert_eq!(summary(&averaged), ["A mean error   4.17 over   3 bursts (predicted 10.00 7.00 7.50, actual 4 8 2)"]);
+
+    // alpha 1 predicts the last burst again, alpha 0 never moves off tau0
+    let last = log("runfor 20\nuse sjf-predict alpha 1 tau0 3\nprocess name A arrival 0 bursts 4,8,2 io 1\nend");
+    assert_eq!(summary(&last), ["A mean error   3.67 over   3 bursts (predicted 3.00 4.00 8.00, actual 4 8 2)"]);
+    let fixed = log("runfor 20\nuse sjf-predict alpha 0 tau0 3\nprocess name A arrival 0 bursts 4,8,2 io 1\nend");
+    assert_eq!(summary(&fixed), ["A mean error   2.33 over   3 bursts (predicted 3.00 3.00 3.00, actual 4 8 2)"]);
+}
+
+#[test]
+fn the_shortest_prediction_runs_first_whoever_joined_first() {
+    // A's first burst of 8 makes it look long, so B's short bursts go ahead of it once both have a history
+    let log = log("runfor 20\nuse sjf-predict alpha 1 tau0 3\n\
+        process name A arrival 0 bursts 8,1\n\
+        process name B arrival 0 bursts 1,1\n\
+        end");
+
+    let selected: Vec<&str> = log.iter().map(String::as_str).filter(|line| line.contains("selected")).collect();
+    assert_eq!(selected, [
+        "Time   0 : A selected (burst   8)",
+        "Time   8 : B selected (burst   1)",
+        "Time   9 : B selected (burst   1)",
+        "Time  10 : A selected (burst   1)",
+    ]);
+    assert_eq!(summary(&log), [
+        "A mean error   6.00 over   2 bursts (predicted 3.00 8.00, actual 8 1)",
+        "B mean error   1.00 over   2 bursts (predicted 3.00 1.00, actual 1 1)",
+    ]);
+}
+
+#[test]
+fn a_process_without_a_full_burst_has_no_error() {
+    let log = log("runfor 5\nuse sjf-predict\nprocess name A arrival 0 burst 3\nprocess name B arrival 0 burst 4\nend");
+    assert_eq!(summary(&log), [
+        "A mean error   7.00 over   1 bursts (predicted 10.00, actual 3)",
+        "B ran no full bursts",
+    ]);
+}
@@ -0,0 +1,159 @@
+// registers a scheduler from outside the crate and checks `use` lines pick it, with its parameters
+
+use std::collections::HashMap;
+use std::sync::Once;
+
+use serde::{Deserialize, Serialize};
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::process::{Process, ProcessState};
+use scheduler_gpt::schedulings::registry::{register, registrations, restore_as, Registration};
+use scheduler_gpt::schedulings::schedule::read_contents;
+use scheduler_gpt::schedulings::scheduler::Scheduler;
+
+/// Longest Job First, non-preemptive, breaking ties between equal bursts towards the first or last name
+#[derive(Clone, Serialize, Deserialize)]
+struct Ljf {
+    last_name_first: bool,
+    waiting: HashMap<String, i32>,
+    selected: Option<String>,
+}
+
+impl Scheduler for Ljf {
+    fn on_arrive(&mut self, process: &mut Process, _: i32) {
+        self.waiting.insert(process.name.to_owned(), process.burst_remaining());
+    }
+
+    fn pre_tick(&mut self, process: &mut Process, time: i32) {
+        if self.selected.is_none() {
+            self.selected = self.waiting.iter()
+                .max_by(|a, b| a.1.cmp(b.1).then_with(|| match self.last_name_first {
+                    true => a.0.cmp(b.0),
+                    false => b.0.cmp(a.0),
+                }))
+                .map(|(name, _)| name.to_owned());
+            if let Some(name) = &self.selected {
+                self.waiting.remove(name);
+            }
+        }
+
+        if self.selected.as_ref() == Some(&process.name) && process.state != Some(ProcessState::Running) {
+            process.select(time);
+        }
+    }
+
+    fn on_tick(&mut self, _: &mut Process, _: i32) {}
+
+    fn on_finish(&mut self, _: &Process, _: i32) {
+        self.selected = None;
+    }
+
+    fn selected_process_name(&self) -> Option<String> {
+        self.selected.clone()
+    }
+
+    fn ready_queue(&self) -> Vec<String> {
+        self.waiting.keys().cloned().collect()
+    }
+
+    fn descriptive_name(&self) -> String {
+        format!("Longest Job First, ties to the {} name", if self.last_name_first { "last" } else { "first" })
+    }
+
+    fn keyword(&self) -> String {
+        "ljf".to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Scheduler> {
+        Box::new(self.clone())
+    }
+
+    fn save_state(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::to_value(self)
+    }
+}
+
+fn ljf() -> Registration {
+    Registration {
+        keyword: "ljf",
+        description: "Longest Job First, the longest waiting burst runs next",
+        params: &["ties"],
+        build: |params| {
+            let last_name_first = match params.get("ties").map(|ties| ties.as_str()) {
+                None | Some(Some("first")) => false,
+                Some(Some("last")) => true,
+                Some(_) => return Err("`ties` must be first or last".into()),
+            };
+            Ok(Box::new(Ljf { last_name_first, waiting: HashMap::new(), selected: None }))
+        },
+        restore: restore_as::<Ljf>,
+    }
+}
+
+/// The registry is shared by every test in the file, so `ljf` goes in once whichever test runs first
+fn register_ljf() {
+    static REGISTERED: Once = Once::new();
+    REGISTERED.call_once(|| register(ljf()).unwrap());
+}
+
+fn log(scenario: &str) -> Vec<String> {
+    let mut model = read_contents(scenario.as_bytes()).unwrap();
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+    cpu.output().to_vec()
+}
+
+fn scenario(use_line: &str) -> String {
+    format!("runfor 12\n{}\n\
+        process name A arrival 0 burst 2\n\
+        process name B arrival 0 burst 4\n\
+        process name C arrival 0 burst 4\n\
+        end", use_line)
+}
+
+#[test]
+fn a_registered_scheduler_can_be_used() {
+    register_ljf();
+    assert!(registrations().iter().any(|r| r.keyword == "ljf"));
+
+    let log = log(&scenario("use ljf"));
+    assert_eq!(log[1], "Using Longest Job First, ties to the first name");
+    let selected: Vec<&str> = log.iter().filter(|line| line.contains("selected")).map(String::as_str).collect();
+    assert_eq!(selected, [
+        "Time   0 : B selected (burst   4)",
+        "Time   4 : C selected (burst   4)",
+        "Time   8 : A selected (burst   2)",
+    ]);
+}
+
+#[test]
+fn a_registered_scheduler_gets_its_parameters() {
+    register_ljf();
+
+    // after the keyword on the `use` line, or as a header line of its own
+    for use_line in ["use ljf ties last", "use ljf\nties last"] {
+        let log = log(&scenario(use_line));
+        assert_eq!(log[1], "Using Longest Job First, ties to the last name");
+        assert!(log.iter().any(|line| line == "Time   0 : C selected (burst   4)"), "{:#?}", log);
+    }
+
+    // a value its build function refuses, and a parameter it does not list
+    let error = |use_line: &str| read_contents(scenario(use_line).as_bytes()).err().unwrap().to_string();
+    assert_eq!(error("use ljf ties 3"), "`ties` must be first or last");
+    assert_eq!(error("use ljf quantum 3"), "scheduler `ljf` does not take a `quantum` parameter");
+}
+
+#[test]
+fn a_keyword_can_only_be_registered_once() {
+    register_ljf();
+
+    let again = register(ljf()).err().unwrap();
+    assert_eq!(again.to_string(), "a scheduler called `ljf` is already registered");
+
+    let built_in = register(Registration { keyword: "fcfs", ..ljf() }).err().unwrap();
+    assert_eq!(built_in.to_string(), "a scheduler called `fcfs` is already registered");
+
+    // the first registration is still the one in use
+    assert_eq!(registrations().iter().filter(|r| r.keyword == "ljf").count(), 1);
+    assert_ne!(registrations().iter().find(|r| r.keyword == "fcfs").unwrap().description, ljf().description);
+}
@@ -0,0 +1,133 @@
+// reads TOML and JSON scenarios, picking the format by extension, and checks where their output goes and what a bad file says
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::scenario::{read_file, Scenario};
+use scheduler_gpt::schedulings::schedule::ScheduleModel;
+
+const TOML: &str = "runfor = 12\n\
+    [scheduler]\nuse = \"rr\"\nquantum = 2\n\
+    [output]\npath = \"results/run.out\"\n\
+    [[processes]]\nname = \"A\"\narrival = 0\nburst = 3\n\
+    [[processes]]\nname = \"B\"\narrival = 1\nbursts = [2, 2]\nafter = [\"A\"]\n";
+
+const JSON: &str = r#"{
+    "runfor": 12,
+    "scheduler": { "use": "rr", "quantum": 2 },
+    "output": { "path": "results/run.out" },
+    "processes": [
+        { "name": "A", "arrival": 0, "burst": 3 },
+        { "name": "B", "arrival": 1, "bursts": [2, 2], "after": ["A"] }
+    ]
+}"#;
+
+const IN: &str = "runfor 12\nuse rr quantum 2\n\
+    process name A arrival 0 burst 3\n\
+    process name B arrival 1 bursts 2,2 after A\n\
+    end\n";
+
+/// A directory of its own under the temp directory for one test
+fn scratch(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("scheduler-gpt-scenario-{}-{}", std::process::id(), test));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write(path: &Path, contents: &str) -> String {
+    fs::write(path, contents).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+fn log(mut model: ScheduleModel) -> Vec<String> {
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+    cpu.output().to_vec()
+}
+
+fn error(result: Result<Scenario, Box<dyn std::error::Error>>) -> String {
+    match result {
+        Ok(_) => panic!("accepted"),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn toml_json_and_in_describe_the_same_run() {
+    let dir = scratch("formats");
+
+    let (toml, _) = read_file(&write(&dir.join("run.toml"), TOML)).unwrap();
+    let (json, _) = read_file(&write(&dir.join("run.json"), JSON)).unwrap();
+    let (plain, _) = read_file(&write(&dir.join("run.in"), IN)).unwrap();
+
+    let expected = log(plain);
+    assert_eq!(log(toml), expected);
+    assert_eq!(log(json), expected);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn the_format_is_picked_by_extension() {
+    let dir = scratch("extensions");
+
+    // TOML with a .json extension is read as JSON, and anything unknown as an .in file
+    assert!(read_file(&write(&dir.join("toml.json"), TOML)).is_err());
+    assert!(read_file(&write(&dir.join("json.toml"), JSON)).is_err());
+    assert!(read_file(&write(&dir.join("toml.txt"), TOML)).is_err());
+    assert!(read_file(&write(&dir.join("plain.txt"), IN)).is_ok());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn a_relative_output_path_is_next_to_the_scenario() {
+    let dir = scratch("output");
+    let sub = dir.join("sub");
+    fs::create_dir_all(&sub).unwrap();
+
+    for file in ["run.toml", "run.json"] {
+        let contents = if file.ends_with("toml") { TOML } else { JSON };
+        let (_, output) = read_file(&write(&sub.join(file), contents)).unwrap();
+        assert_eq!(output.path.map(PathBuf::from), Some(sub.join("results/run.out")), "{}", file);
+    }
+
+    // an absolute path and stdout are left as they are
+    let absolute = dir.join("elsewhere.out");
+    let toml = TOML.replace("results/run.out", &absolute.to_string_lossy());
+    let (_, output) = read_file(&write(&sub.join("absolute.toml"), &toml)).unwrap();
+    assert_eq!(output.path.map(PathBuf::from), Some(absolute));
+
+    let (_, output) = read_file(&write(&sub.join("stdout.toml"), &TOML.replace("results/run.out", "-"))).unwrap();
+    assert_eq!(output.path.as_deref(), Some("-"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unknown_fields_are_named() {
+    let cases = [
+        ("runfor = 5\ncolour = \"red\"\nprocesses = []\n[scheduler]\nuse = \"fcfs\"\n", "unknown field `colour`"),
+        ("runfor = 5\nprocesses = [{ name = \"A\", arrival = 0, burst = 1, colour = \"red\" }]\n[scheduler]\nuse = \"fcfs\"\n", "unknown field `colour`"),
+        ("runfor = 5\nprocesses = []\n[scheduler]\nuse = \"fcfs\"\n[output]\nfile = \"x.out\"\n", "unknown field `file`"),
+    ];
+    for (toml, expected) in cases {
+        let message = error(Scenario::from_toml(toml));
+        assert!(message.contains(expected), "{:?} said {:?}", toml, message);
+    }
+
+    let message = error(Scenario::from_json(r#"{ "runfor": 5, "scheduler": { "use": "fcfs" }, "processes": [], "colour": "red" }"#));
+    assert!(message.contains("unknown field `colour`"), "{}", message);
+
+    // the scheduler table takes whatever parameters the scheduler does
+    let dir = scratch("unknown");
+    let file = write(&dir.join("run.toml"), "runfor = 5\nprocesses = []\n[scheduler]\nuse = \"fcfs\"\nquantum = 3\n");
+    match read_file(&file) {
+        Ok(_) => panic!("accepted a quantum for fcfs"),
+        Err(e) => assert_eq!(e.to_string(), "scheduler `fcfs` does not take a `quantum` parameter"),
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,74 @@
+// runs the sample scenarios with the log streamed out as it is written and checks it matches the one kept in memory
+
+mod common;
+
+use std::env;
+use std::fs::{self, File};
+use std::io;
+
+use common::sample_files;
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::debugger::Debugger;
+use scheduler_gpt::schedulings::scenario::read_file;
+use scheduler_gpt::schedulings::sink::{DiscardSink, WriteSink};
+use scheduler_gpt::schedulings::snapshot::Checkpoint;
+use scheduler_gpt::schedulings::timeline::Timeline;
+
+fn samples() -> Vec<String> {
+    sample_files().into_iter().map(|path| path.to_string_lossy().into_owned()).collect()
+}
+
+/// A run with the log kept in memory, the way every run used to write it
+fn in_memory(file: &str) -> CPU {
+    let (mut model, _) = read_file(file).unwrap();
+    let mut cpu = CPU { verbose: true, ..CPU::default() };
+    cpu.run(&mut model);
+
+    cpu
+}
+
+#[test]
+fn a_streamed_log_matches_the_one_in_memory() {
+    let path = env::temp_dir().join(format!("scheduler-gpt-sink-{}.out", std::process::id()));
+
+    for file in samples() {
+        let (mut model, _) = read_file(&file).unwrap();
+        let mut cpu = CPU::with_sink(Box::new(WriteSink::new(File::create(&path).unwrap())));
+        cpu.verbose = true;
+        cpu.run(&mut model);
+        cpu.flush().unwrap();
+
+        assert!(cpu.output().is_empty(), "{}: the streamed lines were kept as well", file);
+        let mut expected = vec![];
+        in_memory(&file).write_output(&mut expected).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), expected, "{}", file);
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_discarded_log_still_counts_its_lines() {
+    for file in samples() {
+        let (mut model, _) = read_file(&file).unwrap();
+        let mut cpu = CPU::with_sink(Box::new(DiscardSink::default()));
+        cpu.verbose = true;
+        cpu.run(&mut model);
+
+        assert_eq!(cpu.line_count(), in_memory(&file).output().len(), "{}", file);
+    }
+}
+
+#[test]
+fn reading_the_log_back_needs_it_in_memory() {
+    let (mut model, _) = read_file("example.in").unwrap();
+    let discarding = || CPU::with_sink(Box::new(DiscardSink::default()));
+
+    let mut debugger = Debugger::new(Box::new(io::empty()), Box::new(io::sink()));
+    assert!(debugger.run(&mut discarding(), &mut model.clone()).is_err());
+    assert!(Timeline::record(&mut discarding(), &mut model.clone()).is_err());
+
+    let mut cpu = discarding();
+    cpu.run(&mut model);
+    assert!(Checkpoint::capture(&cpu, &model, 3).is_err());
+}
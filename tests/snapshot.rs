@@ -0,0 +1,94 @@
+// stops runs part way, through a checkpoint file or by rewinding the debugger, and checks they end the same as an uninterrupted run
+
+use std::env;
+use std::fs;
+use std::io::{self, BufReader};
+
+use serde_json::Value;
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::debugger::Debugger;
+use scheduler_gpt::schedulings::process::Process;
+use scheduler_gpt::schedulings::registry::registrations;
+use scheduler_gpt::schedulings::schedule::ScheduleModel;
+use scheduler_gpt::schedulings::snapshot::Checkpoint;
+use scheduler_gpt::schedulings::workload::{build, horizon, model, schedulers, WorkloadSpec};
+
+fn spec() -> WorkloadSpec {
+    WorkloadSpec { processes: 25, max_bursts: 2, dependency_percent: 20, ..WorkloadSpec::default() }
+}
+
+fn fresh(keyword: &str, params: &Value, processes: &[Process]) -> ScheduleModel {
+    model(processes.to_vec(), build(keyword, params).unwrap(), horizon(processes)).unwrap()
+}
+
+/// The log and every process once the model has run to the end without stopping
+fn uninterrupted(keyword: &str, params: &Value, processes: &[Process]) -> (Vec<String>, Value) {
+    let mut model = fresh(keyword, params, processes);
+    let mut cpu = CPU::default();
+    cpu.run(&mut model);
+
+    (cpu.output().to_vec(), serde_json::to_value(&model.process_list).unwrap())
+}
+
+#[test]
+fn every_scheduler_covered() {
+    let covered: Vec<&str> = schedulers().iter().map(|(keyword, _)| *keyword).collect();
+
+    for registration in registrations() {
+        assert!(covered.contains(&registration.keyword), "{} has no configuration to checkpoint", registration.keyword);
+    }
+}
+
+#[test]
+fn a_resumed_checkpoint_ends_like_an_uninterrupted_run() {
+    let path = env::temp_dir().join(format!("scheduler-gpt-snapshot-{}.ckpt", std::process::id()));
+    let path = path.to_string_lossy();
+
+    for (keyword, params) in schedulers() {
+        for seed in 0..5 {
+            let processes = spec().for_scheduler(keyword).generate(seed);
+            let expected = uninterrupted(keyword, &params, &processes);
+            let last = horizon(&processes) - 1;
+
+            for time in [0, 1, last / 3, last / 2, last] {
+                let mut model = fresh(keyword, &params, &processes);
+                let mut cpu = CPU::default();
+                cpu.start(&model);
+                for t in 0..=time {
+                    cpu.step(&mut model, t);
+                }
+                Checkpoint::capture(&cpu, &model, time).unwrap().save(&path).unwrap();
+
+                let (mut cpu, mut model) = Checkpoint::load(&path).unwrap().restore().unwrap();
+                cpu.resume(&mut model, time + 1);
+
+                let actual = (cpu.output().to_vec(), serde_json::to_value(&model.process_list).unwrap());
+                assert!(actual == expected, "{} {} seed {} resumed after time {}", keyword, params, seed, time);
+            }
+        }
+    }
+
+    fs::remove_file(path.as_ref()).unwrap();
+}
+
+#[test]
+fn rewinding_the_debugger_ends_like_an_uninterrupted_run() {
+    for (keyword, params) in schedulers() {
+        let processes = WorkloadSpec { processes: 40, ..spec() }.for_scheduler(keyword).generate(3);
+        let expected = uninterrupted(keyword, &params, &processes);
+        let last = horizon(&processes) - 1;
+        // far enough back to cross a keyframe or two, then on to the end
+        assert!(last > 200);
+
+        let commands = format!("until {}\nback 150\nback 1\nc\n", last - 10);
+        let mut debugger = Debugger::new(Box::new(BufReader::new(io::Cursor::new(commands))), Box::new(io::sink()));
+
+        let mut model = fresh(keyword, &params, &processes);
+        let mut cpu = CPU::default();
+        debugger.run(&mut cpu, &mut model).unwrap();
+
+        let actual = (cpu.output().to_vec(), serde_json::to_value(&model.process_list).unwrap());
+        assert!(actual == expected, "{} {} rewound", keyword, params);
+    }
+}
@@ -0,0 +1,35 @@
+// records runs for playback and checks the processes it replays from keyframes are the ones the run went through
+
+use serde_json::{json, Value};
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::timeline::Timeline;
+use scheduler_gpt::schedulings::workload::{build, horizon, model, WorkloadSpec};
+
+#[test]
+fn replayed_frames_match_the_run() {
+    let spec = WorkloadSpec { processes: 30, max_bursts: 2, dependency_percent: 20, ..WorkloadSpec::default() };
+
+    for (keyword, params) in [("sjf", json!({})), ("rr", json!({ "quantum": 3 })), ("custom", json!({ "score": "remaining - wait preemptive" }))] {
+        let processes = spec.generate(5);
+        let time_units = horizon(&processes);
+        // well past a few keyframes
+        assert!(time_units > 200);
+
+        let mut expected: Vec<Value> = vec![];
+        let mut direct = model(processes.clone(), build(keyword, &params).unwrap(), time_units).unwrap();
+        CPU::default().run_with(&mut direct, |_, model, _| expected.push(serde_json::to_value(&model.process_list).unwrap()));
+
+        let mut recorded = model(processes, build(keyword, &params).unwrap(), time_units).unwrap();
+        let timeline = Timeline::record(&mut CPU::default(), &mut recorded).unwrap();
+        assert_eq!(timeline.frames.len(), expected.len());
+
+        for index in [0, 1, 63, 64, 65, 130, expected.len() - 1] {
+            assert_eq!(serde_json::to_value(timeline.processes_at(index)).unwrap(), expected[index], "{} at frame {}", keyword, index);
+        }
+
+        let mut replayed = vec![];
+        timeline.replay(60, 140, |processes| replayed.push(serde_json::to_value(processes).unwrap()));
+        assert_eq!(replayed, expected[60..=140], "{}", keyword);
+    }
+}
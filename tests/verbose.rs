@@ -0,0 +1,46 @@
+// checks the preemption and quantum lines --verbose adds to the log, and that they stay out of it otherwise
+
+use scheduler_gpt::schedulings::cpu::CPU;
+use scheduler_gpt::schedulings::schedule::read_contents;
+
+fn log(input: &str, verbose: bool) -> Vec<String> {
+    let mut model = read_contents(input.as_bytes()).unwrap();
+    let mut cpu = CPU { verbose, ..CPU::default() };
+    cpu.run(&mut model);
+    cpu.output().to_vec()
+}
+
+#[test]
+fn a_preemption_names_what_is_left_of_the_burst() {
+    // A has 3 of its first burst left when B takes over, and 3 more in its second burst
+    let input = "runfor 15\nuse sjf\n\
+        process name A arrival 0 bursts 5,3\n\
+        process name B arrival 2 burst 1\n\
+        end";
+    let verbose = log(input, true);
+
+    let at_two: Vec<&str> = verbose.iter().filter(|line| line.starts_with("Time   2 :")).map(String::as_str).collect();
+    assert_eq!(at_two, [
+        "Time   2 : B arrived",
+        "Time   2 : A preempted by B (remaining 3)",
+        "Time   2 : B selected (burst   1)",
+    ]);
+    assert!(verbose.iter().any(|line| line == "Time   3 : A selected (burst   3)"), "{:#?}", verbose);
+
+    let quiet = log(input, false);
+    assert!(!quiet.iter().any(|line| line.contains("preempted")));
+    assert_eq!(quiet.len(), verbose.len() - 1);
+}
+
+#[test]
+fn an_expired_quantum_is_logged() {
+    let input = "runfor 10\nuse rr quantum 2\n\
+        process name A arrival 0 burst 3\n\
+        process name B arrival 0 burst 2\n\
+        end";
+    let verbose = log(input, true);
+
+    let at_two: Vec<&str> = verbose.iter().filter(|line| line.starts_with("Time   2 :")).map(String::as_str).collect();
+    assert_eq!(at_two, ["Time   2 : A quantum expired", "Time   2 : B selected (burst   2)"]);
+    assert!(!log(input, false).iter().any(|line| line.contains("quantum expired")));
+}